@@ -33,6 +33,10 @@ impl AdjListBuilder {
     pub fn num_edge(&self) -> usize {
         self.adj.len()
     }
+    pub fn add_vert(&mut self) -> usize {
+        self.heads.push(NIL);
+        self.heads.len() - 1
+    }
     pub fn edge(&mut self, from: usize, to: usize) -> &mut Self {
         assert!(
             from < self.num_vert(),
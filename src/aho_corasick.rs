@@ -5,15 +5,37 @@ use crate::trie::*;
 pub struct AhoCorasick {
     trie: Trie,
     suf_link: Vec<usize>,
+    dict_link: Vec<usize>,
     depth: Vec<usize>,
+    order: Vec<usize>,
+    ends: Vec<Vec<usize>>,
+    pattern_node: Vec<usize>,
 }
 
 impl AhoCorasick {
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let mut trie = Trie::new();
+        let pattern_node: Vec<usize> = patterns
+            .into_iter()
+            .map(|p| trie.insert_slice(p.as_ref()).1)
+            .collect();
+        Self::with_patterns(trie, pattern_node)
+    }
+
     pub fn from_trie(trie: Trie) -> Self {
+        let pattern_node = (0..trie.count_node())
+            .flat_map(|v| std::iter::repeat_n(v, trie.end_count(v)))
+            .collect();
+        Self::with_patterns(trie, pattern_node)
+    }
+
+    fn with_patterns(trie: Trie, pattern_node: Vec<usize>) -> Self {
         let mut suf_link = vec![0; trie.count_node()];
         let mut depth = vec![0; trie.count_node()];
+        let mut order = Vec::with_capacity(trie.count_node());
         let mut que: VecDeque<usize> = vec![0].into();
         while let Some(u) = que.pop_front() {
+            order.push(u);
             for (c, v) in trie.links(u) {
                 if u != 0 {
                     let mut a = u;
@@ -33,13 +55,38 @@ impl AhoCorasick {
             }
         }
 
-        Self {
+        let mut ends = vec![vec![]; trie.count_node()];
+        for (id, &v) in pattern_node.iter().enumerate() {
+            ends[v].push(id);
+        }
+        let mut ac = Self {
+            dict_link: vec![!0; trie.count_node()],
+            ends,
             trie,
             suf_link,
             depth,
+            order,
+            pattern_node,
+        };
+        ac.build_dict_link();
+        ac
+    }
+
+    fn build_dict_link(&mut self) {
+        for &v in &self.order[1..] {
+            let s = self.suf_link[v];
+            self.dict_link[v] = if !self.ends[s].is_empty() {
+                s
+            } else {
+                self.dict_link[s]
+            };
         }
     }
 
+    pub fn count_node(&self) -> usize {
+        self.trie.count_node()
+    }
+
     pub fn transition(&self, mut i: usize, c: u8) -> Option<usize> {
         loop {
             if let Some(j) = self.trie.transition(i, c) {
@@ -49,6 +96,10 @@ impl AhoCorasick {
         }
     }
 
+    pub fn next(&self, i: usize, c: u8) -> usize {
+        self.transition(i, c).unwrap_or(0)
+    }
+
     pub fn depth(&self, i: usize) -> usize {
         self.depth[i]
     }
@@ -56,4 +107,95 @@ impl AhoCorasick {
     pub fn suffix(&self, i: usize) -> Option<usize> {
         (i != 0).then(|| self.suf_link[i])
     }
+
+    pub fn output(&self, i: usize) -> Option<usize> {
+        let j = self.dict_link[i];
+        (j != !0).then_some(j)
+    }
+
+    pub fn pattern_count(&self) -> usize {
+        self.pattern_node.len()
+    }
+
+    pub fn pattern_node(&self, id: usize) -> usize {
+        self.pattern_node[id]
+    }
+
+    pub fn patterns_at(&self, i: usize) -> &[usize] {
+        &self.ends[i]
+    }
+
+    pub fn goto_table(&self, alphabet: &[u8]) -> Vec<Vec<usize>> {
+        let mut table = vec![vec![0; alphabet.len()]; self.count_node()];
+        for &v in &self.order {
+            for (k, &c) in alphabet.iter().enumerate() {
+                table[v][k] = match self.trie.transition(v, c) {
+                    Some(w) => w,
+                    None if v == 0 => 0,
+                    None => table[self.suf_link[v]][k],
+                };
+            }
+        }
+        table
+    }
+
+    pub fn find_iter<'a>(&'a self, text: &'a [u8]) -> Matches<'a> {
+        Matches {
+            ac: self,
+            text,
+            pos: 0,
+            state: 0,
+            out: if self.ends[0].is_empty() { !0 } else { 0 },
+            k: 0,
+        }
+    }
+
+    pub fn count_matches(&self, text: &[u8]) -> Vec<usize> {
+        let mut cnt = vec![0; self.count_node()];
+        let mut v = 0;
+        for &c in text {
+            v = self.next(v, c);
+            cnt[v] += 1;
+        }
+        for &v in self.order[1..].iter().rev() {
+            cnt[self.suf_link[v]] += cnt[v];
+        }
+        cnt[0] = text.len() + 1;
+        self.pattern_node.iter().map(|&v| cnt[v]).collect()
+    }
+}
+
+pub struct Matches<'a> {
+    ac: &'a AhoCorasick,
+    text: &'a [u8],
+    pos: usize,
+    state: usize,
+    out: usize,
+    k: usize,
+}
+
+impl Iterator for Matches<'_> {
+    type Item = (usize, usize);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.out != !0 {
+                if let Some(&id) = self.ac.ends[self.out].get(self.k) {
+                    self.k += 1;
+                    return Some((self.pos - self.ac.depth[self.out], id));
+                }
+                self.out = self.ac.dict_link[self.out];
+                self.k = 0;
+                continue;
+            }
+            let &c = self.text.get(self.pos)?;
+            self.state = self.ac.next(self.state, c);
+            self.pos += 1;
+            self.out = if self.ac.ends[self.state].is_empty() {
+                self.ac.dict_link[self.state]
+            } else {
+                self.state
+            };
+            self.k = 0;
+        }
+    }
 }
@@ -0,0 +1,182 @@
+type Idx = u32;
+const NIL: Idx = !0;
+
+#[derive(Clone, Copy)]
+struct Node {
+    children: [Idx; 2],
+    count: usize,
+}
+
+#[derive(Clone)]
+pub struct BinaryTrie {
+    nodes: Vec<Node>,
+    bits: u32,
+}
+
+impl BinaryTrie {
+    pub fn new(bits: u32) -> Self {
+        assert!(bits <= 64);
+        Self {
+            nodes: vec![Node {
+                children: [NIL; 2],
+                count: 0,
+            }],
+            bits,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    pub fn count_node(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn check(&self, x: u64) {
+        assert!(
+            self.bits == 64 || x >> self.bits == 0,
+            "key out of range (bits = {}, key = {x})",
+            self.bits
+        );
+    }
+
+    fn child(&self, i: Idx, b: u64) -> Idx {
+        self.nodes[i as usize].children[b as usize]
+    }
+
+    fn count_at(&self, i: Idx) -> usize {
+        if i == NIL {
+            0
+        } else {
+            self.nodes[i as usize].count
+        }
+    }
+
+    pub fn insert(&mut self, x: u64) {
+        self.insert_n(x, 1);
+    }
+
+    pub fn insert_n(&mut self, x: u64, n: usize) {
+        self.check(x);
+        let mut i = 0;
+        self.nodes[0].count += n;
+        for d in (0..self.bits).rev() {
+            let b = x >> d & 1;
+            let mut c = self.child(i, b);
+            if c == NIL {
+                c = self.nodes.len() as Idx;
+                self.nodes.push(Node {
+                    children: [NIL; 2],
+                    count: 0,
+                });
+                self.nodes[i as usize].children[b as usize] = c;
+            }
+            self.nodes[c as usize].count += n;
+            i = c;
+        }
+    }
+
+    pub fn remove_one(&mut self, x: u64) -> bool {
+        if self.count(x) == 0 {
+            return false;
+        }
+        let mut i = 0;
+        self.nodes[0].count -= 1;
+        for d in (0..self.bits).rev() {
+            i = self.child(i, x >> d & 1);
+            self.nodes[i as usize].count -= 1;
+        }
+        true
+    }
+
+    pub fn count(&self, x: u64) -> usize {
+        self.check(x);
+        let mut i = 0;
+        for d in (0..self.bits).rev() {
+            i = self.child(i, x >> d & 1);
+            if i == NIL {
+                return 0;
+            }
+        }
+        self.nodes[i as usize].count
+    }
+
+    pub fn contains(&self, x: u64) -> bool {
+        self.count(x) > 0
+    }
+
+    pub fn count_lt(&self, x: u64) -> usize {
+        if self.bits < 64 && x >> self.bits != 0 {
+            return self.len();
+        }
+        let mut i = 0;
+        let mut res = 0;
+        for d in (0..self.bits).rev() {
+            let b = x >> d & 1;
+            if b == 1 {
+                res += self.count_at(self.child(i, 0));
+            }
+            i = self.child(i, b);
+            if i == NIL {
+                break;
+            }
+        }
+        res
+    }
+
+    pub fn kth(&self, k: usize) -> Option<u64> {
+        self.kth_xor(k, 0)
+    }
+
+    pub fn kth_xor(&self, mut k: usize, mask: u64) -> Option<u64> {
+        if k >= self.len() {
+            return None;
+        }
+        let mut i = 0;
+        let mut x = 0;
+        for d in (0..self.bits).rev() {
+            let b = mask >> d & 1;
+            let c = self.count_at(self.child(i, b));
+            if k < c {
+                i = self.child(i, b);
+            } else {
+                k -= c;
+                i = self.child(i, b ^ 1);
+                x |= 1 << d;
+            }
+        }
+        Some(x ^ mask)
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.kth(0)
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        self.len().checked_sub(1).and_then(|k| self.kth(k))
+    }
+
+    pub fn prev(&self, x: u64) -> Option<u64> {
+        self.count_lt(x).checked_sub(1).and_then(|k| self.kth(k))
+    }
+
+    pub fn next(&self, x: u64) -> Option<u64> {
+        let k = match x.checked_add(1) {
+            Some(x) => self.count_lt(x),
+            None => self.len(),
+        };
+        self.kth(k)
+    }
+
+    pub fn xor_min(&self, x: u64) -> Option<u64> {
+        self.kth_xor(0, x).map(|y| x ^ y)
+    }
+
+    pub fn xor_max(&self, x: u64) -> Option<u64> {
+        self.len()
+            .checked_sub(1)
+            .and_then(|k| self.kth_xor(k, x))
+            .map(|y| x ^ y)
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use crate::{adj_list::*, scc::*};
+
 pub struct BipartiteMatching {
     g: Vec<Vec<usize>>,
     h: Vec<usize>,
@@ -83,6 +85,157 @@ impl BipartiteMatching {
             .enumerate()
             .filter_map(|(u, &v)| (v < self.g.len()).then_some((u, v)))
     }
+
+    pub fn min_vertex_cover(&self) -> (Vec<usize>, Vec<usize>) {
+        let (left, right) = self.reach_from_free_right();
+        (
+            (0..self.h.len()).filter(|&u| left[u]).collect(),
+            (0..self.g.len()).filter(|&v| !right[v]).collect(),
+        )
+    }
+
+    pub fn max_independent_set(&self) -> (Vec<usize>, Vec<usize>) {
+        let (left, right) = self.reach_from_free_right();
+        (
+            (0..self.h.len()).filter(|&u| !left[u]).collect(),
+            (0..self.g.len()).filter(|&v| right[v]).collect(),
+        )
+    }
+
+    pub fn dulmage_mendelsohn(&self) -> DmDecomposition {
+        let n = self.h.len();
+        let m = self.g.len();
+        let mate = self.right_mates();
+
+        let (left_r, right_r) = self.reach_from_free_right();
+
+        let mut adj = vec![vec![]; n];
+        for (v, g) in self.g.iter().enumerate() {
+            for &u in g {
+                adj[u].push(v);
+            }
+        }
+        let mut left_l = vec![false; n];
+        let mut right_l = vec![false; m];
+        let mut que: VecDeque<usize> = (0..n).filter(|&u| self.h[u] == !0).collect();
+        for &u in &que {
+            left_l[u] = true;
+        }
+        while let Some(u) = que.pop_front() {
+            for &v in &adj[u] {
+                if right_l[v] {
+                    continue;
+                }
+                right_l[v] = true;
+                let w = mate[v];
+                if w != !0 && !left_l[w] {
+                    left_l[w] = true;
+                    que.push_back(w);
+                }
+            }
+        }
+
+        let mut arcs = AdjListBuilder::new(n + m);
+        for (v, g) in self.g.iter().enumerate() {
+            for &u in g {
+                if self.h[u] == v {
+                    arcs.edge(u, n + v);
+                }
+                arcs.edge(n + v, u);
+            }
+        }
+        let arcs = arcs.build();
+        let scc = scc(n + m, |u| arcs.adj(u));
+
+        let is_mid = |x: usize| {
+            if x < n {
+                !left_l[x] && !left_r[x]
+            } else {
+                !right_l[x - n] && !right_r[x - n]
+            }
+        };
+        let mut ids: Vec<usize> = (0..n + m).filter(|&x| is_mid(x)).map(|x| scc[x]).collect();
+        ids.sort_unstable_by(|a, b| b.cmp(a));
+        ids.dedup();
+        let count = ids.len() + 2;
+        let comp = |x: usize| {
+            if is_mid(x) {
+                1 + ids.binary_search_by(|id| scc[x].cmp(id)).unwrap()
+            } else if x < n && left_l[x] || x >= n && right_l[x - n] {
+                0
+            } else {
+                count - 1
+            }
+        };
+
+        let left: Vec<usize> = (0..n).map(comp).collect();
+        let right: Vec<usize> = (n..n + m).map(comp).collect();
+        let mut size = vec![0; count];
+        for &c in left.iter().chain(&right) {
+            size[c] += 1;
+        }
+        DmDecomposition {
+            left,
+            right,
+            count,
+            size,
+        }
+    }
+
+    fn right_mates(&self) -> Vec<usize> {
+        let mut mate = vec![!0; self.g.len()];
+        for (u, v) in self.matches() {
+            mate[v] = u;
+        }
+        mate
+    }
+
+    fn reach_from_free_right(&self) -> (Vec<bool>, Vec<bool>) {
+        let mut left = vec![false; self.h.len()];
+        let mut right = vec![false; self.g.len()];
+        let mut que: VecDeque<usize> = self
+            .right_mates()
+            .iter()
+            .enumerate()
+            .filter_map(|(v, &u)| (u == !0).then_some(v))
+            .collect();
+        for &v in &que {
+            right[v] = true;
+        }
+        while let Some(v) = que.pop_front() {
+            for &u in &self.g[v] {
+                if left[u] {
+                    continue;
+                }
+                left[u] = true;
+                let w = self.h[u];
+                if w != !0 && !right[w] {
+                    right[w] = true;
+                    que.push_back(w);
+                }
+            }
+        }
+        (left, right)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DmDecomposition {
+    pub left: Vec<usize>,
+    pub right: Vec<usize>,
+    pub count: usize,
+    size: Vec<usize>,
+}
+
+impl DmDecomposition {
+    pub fn can_match(&self, u: usize, v: usize) -> bool {
+        self.left[u] == self.right[v]
+    }
+
+    pub fn must_match(&self, u: usize, v: usize) -> bool {
+        let c = self.left[u];
+        c == self.right[v] && 0 < c && c + 1 < self.count && self.size[c] == 2
+    }
 }
 
 struct Dfs<'a> {
@@ -100,7 +253,7 @@ impl Dfs<'_> {
                 self.dist[u] = !0;
                 return true;
             }
-            if self.dist[u] + 1 == self.dist[v] {
+            if self.dist[u] + 1 != self.dist[v] {
                 continue;
             }
             if self.dfs(v) {
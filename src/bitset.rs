@@ -1,4 +1,11 @@
-use std::fmt::{self, Display};
+use std::{
+    fmt::{self, Display},
+    ops::{
+        BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Bound, Not, RangeBounds,
+        Shl, ShlAssign, Shr, ShrAssign,
+    },
+    str::FromStr,
+};
 
 pub trait BitSet {
     type Chunk;
@@ -15,6 +22,11 @@ pub trait BitSet {
     fn reverse_bits(&mut self);
     fn display_bits(&self) -> DisplayBits<&Self>;
     fn one_positions(&self) -> OnePositions<&Self>;
+    fn find_first(&self) -> Option<usize>;
+    fn find_next(&self, i: usize) -> Option<usize>;
+    fn set_range(&mut self, range: impl RangeBounds<usize>, f: bool);
+    fn shl_bits(&mut self, k: usize);
+    fn shr_bits(&mut self, k: usize);
 }
 
 macro_rules! bitset {
@@ -104,6 +116,76 @@ macro_rules! bitset {
                     j: self.len_bits(),
                 }
             }
+            #[inline]
+            fn find_first(&self) -> Option<usize> {
+                let q = self.iter().position(|&x| x != 0)?;
+                Some(<$ty>::BITS as usize * q + self[q].trailing_zeros() as usize)
+            }
+            #[inline]
+            fn find_next(&self, i: usize) -> Option<usize> {
+                let i = i + 1;
+                let q = i / <$ty>::BITS as usize;
+                let masked = self.get(q)? & (!0 << i % <$ty>::BITS as usize);
+                if masked != 0 {
+                    return Some(<$ty>::BITS as usize * q + masked.trailing_zeros() as usize);
+                }
+                self[q + 1..]
+                    .find_first()
+                    .map(|j| <$ty>::BITS as usize * (q + 1) + j)
+            }
+            fn set_range(&mut self, range: impl RangeBounds<usize>, f: bool) {
+                let mut l = match range.start_bound() {
+                    Bound::Included(&l) => l,
+                    Bound::Excluded(&l) => l + 1,
+                    Bound::Unbounded => 0,
+                };
+                let r = match range.end_bound() {
+                    Bound::Included(&r) => r + 1,
+                    Bound::Excluded(&r) => r,
+                    Bound::Unbounded => self.len_bits(),
+                };
+                assert!(
+                    l <= r && r <= self.len_bits(),
+                    "out of range (len = {}, l = {l}, r = {r})",
+                    self.len_bits()
+                );
+                let b = <$ty>::BITS as usize;
+                while l < r {
+                    let q = l / b;
+                    let lo = l % b;
+                    let hi = (r - b * q).min(b);
+                    let mask = <$ty>::MAX >> b - (hi - lo) << lo;
+                    if f {
+                        self[q] |= mask;
+                    } else {
+                        self[q] &= !mask;
+                    }
+                    l = b * q + hi;
+                }
+            }
+            fn shl_bits(&mut self, k: usize) {
+                let b = <$ty>::BITS as usize;
+                let (q, r) = (k / b, k % b);
+                for i in (0..self.len()).rev() {
+                    let mut x = if i >= q { self[i - q] << r } else { 0 };
+                    if r > 0 && i > q {
+                        x |= self[i - q - 1] >> b - r;
+                    }
+                    self[i] = x;
+                }
+            }
+            fn shr_bits(&mut self, k: usize) {
+                let b = <$ty>::BITS as usize;
+                let (q, r) = (k / b, k % b);
+                let n = self.len();
+                for i in 0..n {
+                    let mut x = if i + q < n { self[i + q] >> r } else { 0 };
+                    if r > 0 && i + q + 1 < n {
+                        x |= self[i + q + 1] << b - r;
+                    }
+                    self[i] = x;
+                }
+            }
         }
         impl Iterator for OnePositions<&[$ty]> {
             type Item = usize;
@@ -151,3 +233,444 @@ pub struct OnePositions<T> {
     i: usize,
     j: usize,
 }
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    fn trim(&mut self) {
+        if !self.len.is_multiple_of(64) {
+            self.words[self.len / 64] &= (1 << self.len % 64) - 1;
+        }
+    }
+}
+
+impl BitSet for BitVec {
+    type Chunk = u64;
+    fn bit(&self, i: usize) -> bool {
+        assert!(i < self.len);
+        self.words.bit(i)
+    }
+    fn set_bit(&mut self, i: usize, f: bool) -> bool {
+        assert!(i < self.len);
+        self.words.set_bit(i, f)
+    }
+    fn flip_bit(&mut self, i: usize) -> bool {
+        assert!(i < self.len);
+        self.words.flip_bit(i)
+    }
+    fn len_bits(&self) -> usize {
+        self.len
+    }
+    fn count_ones(&self) -> usize {
+        self.words.count_ones()
+    }
+    fn not(&mut self) {
+        self.words.not();
+        self.trim();
+    }
+    fn and(&mut self, other: &Self) {
+        assert_eq!(self.len, other.len);
+        self.words.and(&other.words);
+    }
+    fn or(&mut self, other: &Self) {
+        assert_eq!(self.len, other.len);
+        self.words.or(&other.words);
+    }
+    fn xor(&mut self, other: &Self) {
+        assert_eq!(self.len, other.len);
+        self.words.xor(&other.words);
+    }
+    fn difference(&mut self, other: &Self) {
+        assert_eq!(self.len, other.len);
+        self.words.difference(&other.words);
+    }
+    fn reverse_bits(&mut self) {
+        self.words.reverse_bits();
+        let k = 64 * self.words.len() - self.len;
+        self.words.shr_bits(k);
+    }
+    fn display_bits(&self) -> DisplayBits<&Self> {
+        DisplayBits(self)
+    }
+    fn one_positions(&self) -> OnePositions<&Self> {
+        OnePositions {
+            data: self,
+            i: 0,
+            j: self.len,
+        }
+    }
+    fn find_first(&self) -> Option<usize> {
+        self.words.find_first()
+    }
+    fn find_next(&self, i: usize) -> Option<usize> {
+        self.words.find_next(i)
+    }
+    fn set_range(&mut self, range: impl RangeBounds<usize>, f: bool) {
+        let r = match range.end_bound() {
+            Bound::Included(&r) => r + 1,
+            Bound::Excluded(&r) => r,
+            Bound::Unbounded => self.len,
+        };
+        assert!(r <= self.len, "out of range (len = {}, r = {r})", self.len);
+        self.words
+            .set_range((range.start_bound().cloned(), Bound::Excluded(r)), f);
+    }
+    fn shl_bits(&mut self, k: usize) {
+        self.words.shl_bits(k);
+        self.trim();
+    }
+    fn shr_bits(&mut self, k: usize) {
+        self.words.shr_bits(k);
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut res = Self::new(0);
+        for f in iter {
+            if res.len.is_multiple_of(64) {
+                res.words.push(0);
+            }
+            res.len += 1;
+            res.set_bit(res.len - 1, f);
+        }
+        res
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bits<const N: usize>(pub [u64; N]);
+
+impl<const N: usize> Bits<N> {
+    pub const ZERO: Self = Self([0; N]);
+}
+
+impl<const N: usize> Default for Bits<N> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const N: usize> BitSet for Bits<N> {
+    type Chunk = u64;
+    fn bit(&self, i: usize) -> bool {
+        self.0.bit(i)
+    }
+    fn set_bit(&mut self, i: usize, f: bool) -> bool {
+        self.0.set_bit(i, f)
+    }
+    fn flip_bit(&mut self, i: usize) -> bool {
+        self.0.flip_bit(i)
+    }
+    fn len_bits(&self) -> usize {
+        64 * N
+    }
+    fn count_ones(&self) -> usize {
+        self.0.count_ones()
+    }
+    fn not(&mut self) {
+        self.0.not();
+    }
+    fn and(&mut self, other: &Self) {
+        self.0.and(&other.0);
+    }
+    fn or(&mut self, other: &Self) {
+        self.0.or(&other.0);
+    }
+    fn xor(&mut self, other: &Self) {
+        self.0.xor(&other.0);
+    }
+    fn difference(&mut self, other: &Self) {
+        self.0.difference(&other.0);
+    }
+    fn reverse_bits(&mut self) {
+        self.0.reverse_bits();
+    }
+    fn display_bits(&self) -> DisplayBits<&Self> {
+        DisplayBits(self)
+    }
+    fn one_positions(&self) -> OnePositions<&Self> {
+        OnePositions {
+            data: self,
+            i: 0,
+            j: 64 * N,
+        }
+    }
+    fn find_first(&self) -> Option<usize> {
+        self.0.find_first()
+    }
+    fn find_next(&self, i: usize) -> Option<usize> {
+        self.0.find_next(i)
+    }
+    fn set_range(&mut self, range: impl RangeBounds<usize>, f: bool) {
+        self.0.set_range(range, f);
+    }
+    fn shl_bits(&mut self, k: usize) {
+        self.0.shl_bits(k);
+    }
+    fn shr_bits(&mut self, k: usize) {
+        self.0.shr_bits(k);
+    }
+}
+
+macro_rules! owned_bitset {
+    ([$($g:tt)*] $t:ty) => {
+        impl<$($g)*> Iterator for OnePositions<&$t> {
+            type Item = usize;
+            fn next(&mut self) -> Option<Self::Item> {
+                let i = if self.i == 0 {
+                    self.data.find_first()
+                } else {
+                    self.data.find_next(self.i - 1)
+                }
+                .filter(|&i| i < self.j)?;
+                self.i = i + 1;
+                Some(i)
+            }
+        }
+        impl<$($g)*> Display for DisplayBits<&$t> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for i in 0..self.0.len_bits() {
+                    write!(f, "{}", self.0.bit(i) as u8)?;
+                }
+                Ok(())
+            }
+        }
+        impl<$($g)*> Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.display_bits().fmt(f)
+            }
+        }
+        impl<$($g)*> BitAndAssign<&$t> for $t {
+            fn bitand_assign(&mut self, rhs: &$t) {
+                self.and(rhs);
+            }
+        }
+        impl<$($g)*> BitOrAssign<&$t> for $t {
+            fn bitor_assign(&mut self, rhs: &$t) {
+                self.or(rhs);
+            }
+        }
+        impl<$($g)*> BitXorAssign<&$t> for $t {
+            fn bitxor_assign(&mut self, rhs: &$t) {
+                self.xor(rhs);
+            }
+        }
+        impl<$($g)*> BitAnd<&$t> for $t {
+            type Output = $t;
+            fn bitand(mut self, rhs: &$t) -> $t {
+                self &= rhs;
+                self
+            }
+        }
+        impl<$($g)*> BitOr<&$t> for $t {
+            type Output = $t;
+            fn bitor(mut self, rhs: &$t) -> $t {
+                self |= rhs;
+                self
+            }
+        }
+        impl<$($g)*> BitXor<&$t> for $t {
+            type Output = $t;
+            fn bitxor(mut self, rhs: &$t) -> $t {
+                self ^= rhs;
+                self
+            }
+        }
+        impl<$($g)*> Not for $t {
+            type Output = $t;
+            fn not(mut self) -> $t {
+                BitSet::not(&mut self);
+                self
+            }
+        }
+        impl<$($g)*> ShlAssign<usize> for $t {
+            fn shl_assign(&mut self, k: usize) {
+                self.shl_bits(k);
+            }
+        }
+        impl<$($g)*> ShrAssign<usize> for $t {
+            fn shr_assign(&mut self, k: usize) {
+                self.shr_bits(k);
+            }
+        }
+        impl<$($g)*> Shl<usize> for $t {
+            type Output = $t;
+            fn shl(mut self, k: usize) -> $t {
+                self <<= k;
+                self
+            }
+        }
+        impl<$($g)*> Shr<usize> for $t {
+            type Output = $t;
+            fn shr(mut self, k: usize) -> $t {
+                self >>= k;
+                self
+            }
+        }
+        impl<$($g)*> Shl<usize> for &$t {
+            type Output = $t;
+            fn shl(self, k: usize) -> $t {
+                self.clone() << k
+            }
+        }
+        impl<$($g)*> Shr<usize> for &$t {
+            type Output = $t;
+            fn shr(self, k: usize) -> $t {
+                self.clone() >> k
+            }
+        }
+    };
+}
+
+owned_bitset!([] BitVec);
+owned_bitset!([const N: usize] Bits<N>);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseBitsError;
+
+impl FromStr for BitVec {
+    type Err = ParseBitsError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.bytes()
+            .map(|c| match c {
+                b'0' => Ok(false),
+                b'1' => Ok(true),
+                _ => Err(ParseBitsError),
+            })
+            .collect()
+    }
+}
+
+impl<const N: usize> FromStr for Bits<N> {
+    type Err = ParseBitsError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v: BitVec = s.parse()?;
+        if v.len > 64 * N {
+            return Err(ParseBitsError);
+        }
+        let mut res = Self::ZERO;
+        res.0[..v.words.len()].copy_from_slice(&v.words);
+        Ok(res)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RankSelect {
+    words: Vec<u64>,
+    rank: Vec<usize>,
+    len: usize,
+}
+
+impl RankSelect {
+    pub fn new(mut words: Vec<u64>, len: usize) -> Self {
+        assert!(len <= words.len_bits());
+        words.truncate(len.div_ceil(64));
+        if !len.is_multiple_of(64) {
+            words[len / 64] &= (1 << len % 64) - 1;
+        }
+        let mut rank = Vec::with_capacity(words.len() + 1);
+        rank.push(0);
+        for x in &words {
+            rank.push(rank.last().unwrap() + x.count_ones() as usize);
+        }
+        Self { words, rank, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        assert!(i < self.len);
+        self.words.bit(i)
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.rank[self.words.len()]
+    }
+
+    pub fn rank1(&self, i: usize) -> usize {
+        assert!(i <= self.len);
+        let (q, r) = (i / 64, i % 64);
+        if r == 0 {
+            self.rank[q]
+        } else {
+            self.rank[q] + (self.words[q] & (1 << r) - 1).count_ones() as usize
+        }
+    }
+
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+
+    pub fn select1(&self, k: usize) -> Option<usize> {
+        if k >= self.count_ones() {
+            return None;
+        }
+        let q = self.rank.partition_point(|&r| r <= k) - 1;
+        Some(64 * q + select_in_word(self.words[q], k - self.rank[q]))
+    }
+
+    pub fn select0(&self, k: usize) -> Option<usize> {
+        if k >= self.len - self.count_ones() {
+            return None;
+        }
+        let zeros = |q: usize| 64 * q - self.rank[q];
+        let (mut lo, mut hi) = (0, self.words.len());
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if zeros(mid) <= k {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Some(64 * lo + select_in_word(!self.words[lo], k - zeros(lo)))
+    }
+}
+
+fn select_in_word(mut x: u64, k: usize) -> usize {
+    for _ in 0..k {
+        x &= x - 1;
+    }
+    x.trailing_zeros() as usize
+}
+
+impl FromIterator<bool> for RankSelect {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut words = vec![];
+        let mut len = 0;
+        for f in iter {
+            if len % 64 == 0 {
+                words.push(0);
+            }
+            words[len / 64] |= (f as u64) << len % 64;
+            len += 1;
+        }
+        Self::new(words, len)
+    }
+}
@@ -1,4 +1,8 @@
-use std::ops::{Add, Div, Mul, Sub};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, VecDeque},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
 
 pub trait Scalar:
     Copy
@@ -7,6 +11,7 @@ pub trait Scalar:
     + Sub<Output = Self>
     + Mul<Output = Self>
     + Div<Output = Self>
+    + Neg<Output = Self>
 {
     type Wide: PartialOrd;
     fn wide_mul(self, other: Self) -> Self::Wide;
@@ -47,51 +52,105 @@ float!(f64);
 
 #[derive(Default, Debug)]
 pub struct Cht<T> {
-    lines: Vec<Line<T>>,
+    lines: VecDeque<Line<T>>,
+    ids: VecDeque<usize>,
+    pushed: usize,
 }
 
 impl<T> Cht<T> {
     pub const fn new() -> Self {
-        Self { lines: vec![] }
+        Self {
+            lines: VecDeque::new(),
+            ids: VecDeque::new(),
+            pushed: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn pop_front(&mut self) -> Option<(usize, Line<T>)> {
+        Some((self.ids.pop_front()?, self.lines.pop_front()?))
+    }
+
+    pub fn pop_back(&mut self) -> Option<(usize, Line<T>)> {
+        Some((self.ids.pop_back()?, self.lines.pop_back()?))
     }
 }
 
 impl<T: Scalar> Cht<T> {
-    pub fn push_line(&mut self, l: Line<T>) {
-        assert!(self.lines.last().is_none_or(|last| last.a >= l.a));
-        if let Some(t) = self.lines.last() {
+    pub fn push_line(&mut self, l: Line<T>) -> usize {
+        let id = self.pushed;
+        self.push_with_id(id, l);
+        id
+    }
+
+    fn push_with_id(&mut self, id: usize, l: Line<T>) {
+        assert!(self.lines.back().is_none_or(|last| last.a >= l.a));
+        self.pushed = self.pushed.max(id + 1);
+        if let Some(t) = self.lines.back() {
             if t.a == l.a {
                 if t.b <= l.b {
                     return;
                 }
-                self.lines.pop();
+                self.pop_back();
             }
         }
-        while let [.., s, t] = &*self.lines {
+        while let n @ 2.. = self.lines.len() {
+            let (s, t) = (self.lines[n - 2], self.lines[n - 1]);
             if (l.b - t.b).wide_mul(s.a - t.a) > (t.b - s.b).wide_mul(t.a - l.a) {
                 break;
             }
-            self.lines.pop();
+            self.pop_back();
         }
-        self.lines.push(l);
+        self.lines.push_back(l);
+        self.ids.push_back(id);
     }
 
-    pub fn y_min(&self, x: T) -> T {
+    fn better_than_next(&self, i: usize, x: T) -> bool {
+        let (s, t) = (self.lines[i], self.lines[i + 1]);
+        x.wide_mul(s.a - t.a) < (t.b - s.b).to_wide()
+    }
+
+    fn find(&self, x: T) -> usize {
         assert!(!self.lines.is_empty());
         let mut l = 0;
         let mut r = self.lines.len();
         while r - l > 1 {
             let i = (l + r) / 2;
-            let [s, t] = &self.lines[i - 1..i + 1] else {
-                unreachable!()
-            };
-            if x.wide_mul(s.a - t.a) < (t.b - s.b).to_wide() {
+            if self.better_than_next(i - 1, x) {
                 r = i;
             } else {
                 l = i;
             }
         }
-        self.lines[l].y(x)
+        l
+    }
+
+    pub fn y_min(&self, x: T) -> T {
+        self.lines[self.find(x)].y(x)
+    }
+
+    pub fn argmin(&self, x: T) -> usize {
+        self.ids[self.find(x)]
+    }
+
+    fn advance(&mut self, x: T) {
+        assert!(!self.lines.is_empty());
+        while self.lines.len() >= 2 && !self.better_than_next(0, x) {
+            self.pop_front();
+        }
+    }
+
+    pub fn y_min_increasing(&mut self, x: T) -> T {
+        self.advance(x);
+        self.lines[0].y(x)
+    }
+
+    pub fn argmin_increasing(&mut self, x: T) -> usize {
+        self.advance(x);
+        self.ids[0]
     }
 }
 
@@ -102,17 +161,239 @@ impl<T: Scalar + Ord> FromIterator<Line<T>> for Cht<T> {
 }
 
 impl<T: Scalar + Ord> From<Vec<Line<T>>> for Cht<T> {
-    fn from(mut lines: Vec<Line<T>>) -> Self {
+    fn from(lines: Vec<Line<T>>) -> Self {
         use std::cmp::Reverse;
-        lines.sort_by_key(|l| Reverse(l.a));
+        let mut order: Vec<usize> = (0..lines.len()).collect();
+        order.sort_by_key(|&i| Reverse(lines[i].a));
+        let mut cht = Self::new();
+        for i in order {
+            cht.push_with_id(i, lines[i]);
+        }
+        cht.pushed = lines.len();
+        cht
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct ChtMax<T>(Cht<T>);
+
+impl<T> ChtMax<T> {
+    pub const fn new() -> Self {
+        Self(Cht::new())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<T: Scalar> ChtMax<T> {
+    pub fn push_line(&mut self, l: Line<T>) -> usize {
+        self.0.push_line(line(-l.a, -l.b))
+    }
+
+    pub fn y_max(&self, x: T) -> T {
+        -self.0.y_min(x)
+    }
+
+    pub fn argmax(&self, x: T) -> usize {
+        self.0.argmin(x)
+    }
+
+    pub fn y_max_increasing(&mut self, x: T) -> T {
+        -self.0.y_min_increasing(x)
+    }
+
+    pub fn argmax_increasing(&mut self, x: T) -> usize {
+        self.0.argmin_increasing(x)
+    }
+}
+
+impl<T: Scalar + Ord> FromIterator<Line<T>> for ChtMax<T> {
+    fn from_iter<I: IntoIterator<Item = Line<T>>>(iter: I) -> Self {
+        Self(iter.into_iter().map(|l| line(-l.a, -l.b)).collect())
+    }
+}
+
+impl<T: Scalar + Ord> From<Vec<Line<T>>> for ChtMax<T> {
+    fn from(lines: Vec<Line<T>>) -> Self {
+        lines.into_iter().collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct DynamicCht<T> {
+    min: Hull<T>,
+    max: Hull<T>,
+}
+
+impl<T: Scalar + Ord> DynamicCht<T> {
+    pub fn new() -> Self {
+        Self {
+            min: Hull::new(),
+            max: Hull::new(),
+        }
+    }
+
+    pub fn insert(&mut self, l: Line<T>) {
+        self.min.insert(l);
+        self.max.insert(line(-l.a, -l.b));
+    }
+
+    pub fn y_min(&self, x: T) -> T {
+        self.min.y_min(x)
+    }
+
+    pub fn y_max(&self, x: T) -> T {
+        -self.max.y_min(x)
+    }
+}
+
+impl<T: Scalar + Ord> Default for DynamicCht<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Scalar + Ord> FromIterator<Line<T>> for DynamicCht<T> {
+    fn from_iter<I: IntoIterator<Item = Line<T>>>(iter: I) -> Self {
         let mut cht = Self::new();
-        for l in lines {
-            cht.push_line(l);
+        for l in iter {
+            cht.insert(l);
         }
         cht
     }
 }
 
+#[derive(Debug)]
+struct Hull<T> {
+    lines: BTreeMap<T, (T, Option<Key<T>>)>,
+    by_x: BTreeMap<Option<Key<T>>, T>,
+}
+
+impl<T: Scalar + Ord> Hull<T> {
+    fn new() -> Self {
+        Self {
+            lines: BTreeMap::new(),
+            by_x: BTreeMap::new(),
+        }
+    }
+
+    fn get(&self, a: T) -> Line<T> {
+        line(a, self.lines[&a].0)
+    }
+
+    fn prev(&self, a: T) -> Option<Line<T>> {
+        let (&a, &(b, _)) = self.lines.range(..a).next_back()?;
+        Some(line(a, b))
+    }
+
+    fn next(&self, a: T) -> Option<Line<T>> {
+        let (&a, &(b, _)) = self.lines.range(a..).find(|&(&c, _)| c != a)?;
+        Some(line(a, b))
+    }
+
+    fn remove(&mut self, a: T) {
+        let (_, key) = self.lines.remove(&a).unwrap();
+        self.by_x.remove(&key);
+    }
+
+    fn update_key(&mut self, a: T) {
+        let key = self.next(a).map(|s| {
+            let t = self.get(a);
+            Key::Frac(t.b - s.b, s.a - t.a)
+        });
+        let entry = self.lines.get_mut(&a).unwrap();
+        let old = std::mem::replace(&mut entry.1, key);
+        if self.by_x.get(&old) == Some(&a) {
+            self.by_x.remove(&old);
+        }
+        self.by_x.insert(key, a);
+    }
+
+    fn insert(&mut self, l: Line<T>) {
+        if let Some(&(b, _)) = self.lines.get(&l.a) {
+            if b <= l.b {
+                return;
+            }
+            self.remove(l.a);
+        }
+        if let (Some(s), Some(u)) = (self.next(l.a), self.prev(l.a))
+            && bad(s, l, u)
+        {
+            return;
+        }
+        while let Some(t) = self.prev(l.a) {
+            match self.prev(t.a) {
+                Some(u) if bad(l, t, u) => self.remove(t.a),
+                _ => break,
+            }
+        }
+        while let Some(t) = self.next(l.a) {
+            match self.next(t.a) {
+                Some(s) if bad(s, t, l) => self.remove(t.a),
+                _ => break,
+            }
+        }
+        self.lines.insert(l.a, (l.b, None));
+        self.update_key(l.a);
+        if let Some(u) = self.prev(l.a) {
+            self.update_key(u.a);
+        }
+    }
+
+    fn y_min(&self, x: T) -> T {
+        let (_, &a) = self
+            .by_x
+            .range(..=Some(Key::X(x)))
+            .next_back()
+            .expect("empty hull");
+        self.get(a).y(x)
+    }
+}
+
+fn bad<T: Scalar>(s: Line<T>, t: Line<T>, u: Line<T>) -> bool {
+    (u.b - t.b).wide_mul(s.a - t.a) <= (t.b - s.b).wide_mul(t.a - u.a)
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Key<T> {
+    Frac(T, T),
+    X(T),
+}
+
+impl<T: Scalar> Key<T> {
+    fn cmp_frac(&self, other: &Self) -> Ordering {
+        let (x, y) = match (*self, *other) {
+            (Key::Frac(a, b), Key::Frac(c, d)) => (a.wide_mul(d), c.wide_mul(b)),
+            (Key::Frac(a, b), Key::X(c)) => (a.to_wide(), c.wide_mul(b)),
+            (Key::X(a), Key::Frac(c, d)) => (a.wide_mul(d), c.to_wide()),
+            (Key::X(a), Key::X(c)) => (a.to_wide(), c.to_wide()),
+        };
+        x.partial_cmp(&y).unwrap()
+    }
+}
+
+impl<T: Scalar> PartialEq for Key<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp_frac(other) == Ordering::Equal
+    }
+}
+
+impl<T: Scalar> Eq for Key<T> {}
+
+impl<T: Scalar> PartialOrd for Key<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Scalar> Ord for Key<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_frac(other)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
 pub struct Line<T> {
     pub a: T,
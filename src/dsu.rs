@@ -27,6 +27,20 @@ impl Dsu {
         }
     }
 
+    pub fn find(&mut self, i: usize) -> usize {
+        let mut r = i;
+        while self.0[r] >= 0 {
+            r = self.0[r] as usize;
+        }
+        let mut i = i;
+        while self.0[i] >= 0 {
+            let p = self.0[i] as usize;
+            self.0[i] = r as IIdx;
+            i = p;
+        }
+        r
+    }
+
     pub fn is_root(&self, i: usize) -> bool {
         self.0[i] < 0
     }
@@ -79,6 +93,266 @@ impl Dsu {
     }
 }
 
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RollbackDsu {
+    dsu: Dsu,
+    history: Vec<Option<(usize, usize, IIdx)>>,
+}
+
+impl RollbackDsu {
+    pub fn new(n: usize) -> Self {
+        Self {
+            dsu: Dsu::new(n),
+            history: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.dsu.len()
+    }
+
+    pub fn comp(&self, i: usize) -> Comp {
+        self.dsu.comp(i)
+    }
+
+    pub fn is_root(&self, i: usize) -> bool {
+        self.dsu.is_root(i)
+    }
+
+    pub fn root(&self, i: usize) -> usize {
+        self.dsu.root(i)
+    }
+
+    pub fn size(&self, i: usize) -> usize {
+        self.dsu.size(i)
+    }
+
+    pub fn unite(&mut self, i: usize, j: usize) -> UniteResult {
+        let ci = self.dsu.comp(i);
+        let res = self.dsu.unite(i, j);
+        self.history.push(res.is_united.then(|| {
+            let size = if res.united_root == ci.root {
+                ci.size
+            } else {
+                res.size - ci.size
+            };
+            (res.root, res.united_root, -(size as IIdx))
+        }));
+        res
+    }
+
+    pub fn time(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(h) = self.history.pop() else {
+            return false;
+        };
+        if let Some((r, c, orig)) = h {
+            self.dsu.0[r] -= orig;
+            self.dsu.0[c] = orig;
+        }
+        true
+    }
+
+    pub fn rollback(&mut self, time: usize) {
+        assert!(time <= self.time());
+        while self.time() > time {
+            self.undo();
+        }
+    }
+
+    pub fn comps(&self) -> Comps<'_> {
+        self.dsu.comps()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PersistentDsu {
+    dsu: Dsu,
+    time: Vec<usize>,
+    sizes: Vec<Vec<(usize, usize)>>,
+    now: usize,
+}
+
+impl PersistentDsu {
+    pub fn new(n: usize) -> Self {
+        Self {
+            dsu: Dsu::new(n),
+            time: vec![!0; n],
+            sizes: vec![vec![(0, 1)]; n],
+            now: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.dsu.len()
+    }
+
+    pub fn now(&self) -> usize {
+        self.now
+    }
+
+    pub fn unite(&mut self, i: usize, j: usize, time: usize) -> UniteResult {
+        assert!(
+            self.now <= time,
+            "time must be non-decreasing (now = {}, time = {time})",
+            self.now
+        );
+        self.now = time;
+        let res = self.dsu.unite(i, j);
+        if res.is_united {
+            self.time[res.united_root] = time;
+            self.sizes[res.root].push((time, res.size));
+        }
+        res
+    }
+
+    pub fn comp_at(&self, mut i: usize, time: usize) -> Comp {
+        while self.dsu.0[i] >= 0 && self.time[i] <= time {
+            i = self.dsu.0[i] as usize;
+        }
+        let sizes = &self.sizes[i];
+        let k = sizes.partition_point(|&(t, _)| t <= time);
+        Comp {
+            root: i,
+            size: sizes[k - 1].1,
+        }
+    }
+
+    pub fn root_at(&self, i: usize, time: usize) -> usize {
+        self.comp_at(i, time).root
+    }
+
+    pub fn size_at(&self, i: usize, time: usize) -> usize {
+        self.comp_at(i, time).size
+    }
+
+    pub fn same_at(&self, i: usize, j: usize, time: usize) -> bool {
+        self.root_at(i, time) == self.root_at(j, time)
+    }
+
+    pub fn comp(&self, i: usize) -> Comp {
+        self.dsu.comp(i)
+    }
+}
+
+pub trait Group {
+    fn id() -> Self;
+    fn op(&self, other: &Self) -> Self;
+    fn inv(&self) -> Self;
+}
+
+macro_rules! group {
+    ($ty:ident) => {
+        impl Group for $ty {
+            fn id() -> Self {
+                0
+            }
+            fn op(&self, other: &Self) -> Self {
+                self.wrapping_add(*other)
+            }
+            fn inv(&self) -> Self {
+                self.wrapping_neg()
+            }
+        }
+    };
+}
+
+group!(i32);
+group!(i64);
+group!(i128);
+group!(isize);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WeightedDsu<G> {
+    dsu: Dsu,
+    pot: Vec<G>,
+}
+
+impl<G: Group> WeightedDsu<G> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            dsu: Dsu::new(n),
+            pot: (0..n).map(|_| G::id()).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.dsu.len()
+    }
+
+    pub fn comp(&self, i: usize) -> (Comp, G) {
+        let mut i = i;
+        let mut pot = G::id();
+        while !self.dsu.is_root(i) {
+            pot = self.pot[i].op(&pot);
+            i = self.dsu.0[i] as usize;
+        }
+        (
+            Comp {
+                root: i,
+                size: (-self.dsu.0[i]) as usize,
+            },
+            pot,
+        )
+    }
+
+    pub fn is_root(&self, i: usize) -> bool {
+        self.dsu.is_root(i)
+    }
+
+    pub fn root(&self, i: usize) -> usize {
+        self.dsu.root(i)
+    }
+
+    pub fn size(&self, i: usize) -> usize {
+        self.dsu.size(i)
+    }
+
+    pub fn potential(&self, i: usize) -> G {
+        self.comp(i).1
+    }
+
+    pub fn diff(&self, i: usize, j: usize) -> Option<G> {
+        let (ci, pi) = self.comp(i);
+        let (cj, pj) = self.comp(j);
+        (ci.root == cj.root).then(|| pi.inv().op(&pj))
+    }
+
+    pub fn unite(&mut self, i: usize, j: usize, w: G) -> Option<UniteResult>
+    where
+        G: PartialEq,
+    {
+        let (ci, pi) = self.comp(i);
+        let (cj, pj) = self.comp(j);
+        if ci.root == cj.root {
+            if pi.inv().op(&pj) != w {
+                return None;
+            }
+            return Some(UniteResult {
+                is_united: false,
+                root: ci.root,
+                united_root: ci.root,
+                size: ci.size,
+            });
+        }
+        let res = self.dsu.unite(i, j);
+        let pot = pi.op(&w).op(&pj.inv());
+        if res.united_root == cj.root {
+            self.pot[cj.root] = pot;
+        } else {
+            self.pot[ci.root] = pot.inv();
+        }
+        Some(res)
+    }
+
+    pub fn comps(&self) -> Comps<'_> {
+        self.dsu.comps()
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct Comp {
     pub root: usize,
@@ -0,0 +1,287 @@
+use std::{
+    mem,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::segtree::Monoid;
+
+type Idx = u32;
+const NIL: Idx = !0;
+
+fn mid(lo: i64, hi: i64) -> i64 {
+    lo + (hi.abs_diff(lo) / 2) as i64
+}
+
+struct Node<T> {
+    value: T,
+    left: Idx,
+    right: Idx,
+}
+
+struct Nodes<T> {
+    nodes: Vec<Node<T>>,
+    lo: i64,
+    hi: i64,
+}
+
+impl<T: Monoid> Nodes<T> {
+    fn new(lo: i64, hi: i64) -> Self {
+        assert!(lo < hi, "empty range ({lo}, {hi})");
+        Self {
+            nodes: vec![],
+            lo,
+            hi,
+        }
+    }
+
+    fn range(&self, range: impl RangeBounds<i64>) -> (i64, i64) {
+        let l = match range.start_bound() {
+            Bound::Included(&l) => l,
+            Bound::Excluded(&l) => l + 1,
+            Bound::Unbounded => self.lo,
+        };
+        let r = match range.end_bound() {
+            Bound::Included(&r) => r + 1,
+            Bound::Excluded(&r) => r,
+            Bound::Unbounded => self.hi,
+        };
+        assert!(
+            self.lo <= l && l <= r && r <= self.hi,
+            "out of range (range = {}..{}, l = {l}, r = {r})",
+            self.lo,
+            self.hi
+        );
+        (l, r)
+    }
+
+    fn push(&mut self, value: T, left: Idx, right: Idx) -> Idx {
+        self.nodes.push(Node { value, left, right });
+        (self.nodes.len() - 1) as Idx
+    }
+
+    fn value(&self, i: Idx) -> T {
+        if i == NIL {
+            T::id()
+        } else {
+            self.nodes[i as usize].value.op(&T::id())
+        }
+    }
+
+    fn pull(&self, left: Idx, right: Idx) -> T {
+        match (left, right) {
+            (NIL, NIL) => T::id(),
+            (NIL, r) => self.value(r),
+            (l, NIL) => self.value(l),
+            (l, r) => self.nodes[l as usize]
+                .value
+                .op(&self.nodes[r as usize].value),
+        }
+    }
+
+    fn child(&self, i: Idx, right: bool) -> Idx {
+        if i == NIL {
+            NIL
+        } else if right {
+            self.nodes[i as usize].right
+        } else {
+            self.nodes[i as usize].left
+        }
+    }
+
+    fn get(&self, mut node: Idx, i: i64) -> T {
+        assert!(self.lo <= i && i < self.hi);
+        let (mut lo, mut hi) = (self.lo, self.hi);
+        while node != NIL && hi.abs_diff(lo) > 1 {
+            let mid = mid(lo, hi);
+            if i < mid {
+                node = self.child(node, false);
+                hi = mid;
+            } else {
+                node = self.child(node, true);
+                lo = mid;
+            }
+        }
+        self.value(node)
+    }
+
+    fn set_in_place(&mut self, node: Idx, lo: i64, hi: i64, i: i64, x: T) -> (Idx, T) {
+        let node = if node == NIL {
+            self.push(T::id(), NIL, NIL)
+        } else {
+            node
+        };
+        if hi.abs_diff(lo) == 1 {
+            let orig = mem::replace(&mut self.nodes[node as usize].value, x);
+            return (node, orig);
+        }
+        let mid = mid(lo, hi);
+        let orig = if i < mid {
+            let (c, orig) = self.set_in_place(self.nodes[node as usize].left, lo, mid, i, x);
+            self.nodes[node as usize].left = c;
+            orig
+        } else {
+            let (c, orig) = self.set_in_place(self.nodes[node as usize].right, mid, hi, i, x);
+            self.nodes[node as usize].right = c;
+            orig
+        };
+        let Node { left, right, .. } = self.nodes[node as usize];
+        self.nodes[node as usize].value = self.pull(left, right);
+        (node, orig)
+    }
+
+    fn set_persistent(&mut self, node: Idx, lo: i64, hi: i64, i: i64, x: T) -> Idx {
+        if hi.abs_diff(lo) == 1 {
+            return self.push(x, NIL, NIL);
+        }
+        let mid = mid(lo, hi);
+        let (mut left, mut right) = (self.child(node, false), self.child(node, true));
+        if i < mid {
+            left = self.set_persistent(left, lo, mid, i, x);
+        } else {
+            right = self.set_persistent(right, mid, hi, i, x);
+        }
+        let value = self.pull(left, right);
+        self.push(value, left, right)
+    }
+
+    fn prod(&self, node: Idx, lo: i64, hi: i64, l: i64, r: i64) -> T {
+        if node == NIL || r <= lo || hi <= l {
+            return T::id();
+        }
+        if l <= lo && hi <= r {
+            return self.value(node);
+        }
+        let mid = mid(lo, hi);
+        let Node { left, right, .. } = self.nodes[node as usize];
+        self.prod(left, lo, mid, l, r)
+            .op(&self.prod(right, mid, hi, l, r))
+    }
+
+    fn max_right(
+        &self,
+        node: Idx,
+        lo: i64,
+        hi: i64,
+        l: i64,
+        f: &mut impl FnMut(&T) -> bool,
+        acc: &mut T,
+    ) -> Option<i64> {
+        if node == NIL || hi <= l {
+            return None;
+        }
+        if l <= lo {
+            let prod = acc.op(&self.nodes[node as usize].value);
+            if f(&prod) {
+                *acc = prod;
+                return None;
+            }
+            if hi.abs_diff(lo) == 1 {
+                return Some(lo);
+            }
+        }
+        let mid = mid(lo, hi);
+        let Node { left, right, .. } = self.nodes[node as usize];
+        self.max_right(left, lo, mid, l, f, acc)
+            .or_else(|| self.max_right(right, mid, hi, l, f, acc))
+    }
+}
+
+pub struct DynamicSegTree<T> {
+    nodes: Nodes<T>,
+    root: Idx,
+}
+
+impl<T: Monoid> DynamicSegTree<T> {
+    pub fn new(lo: i64, hi: i64) -> Self {
+        Self {
+            nodes: Nodes::new(lo, hi),
+            root: NIL,
+        }
+    }
+
+    pub fn count_node(&self) -> usize {
+        self.nodes.nodes.len()
+    }
+
+    pub fn get(&self, i: i64) -> T {
+        self.nodes.get(self.root, i)
+    }
+
+    pub fn set(&mut self, i: i64, x: T) -> T {
+        let Nodes { lo, hi, .. } = self.nodes;
+        assert!(
+            lo <= i && i < hi,
+            "out of range (range = {lo}..{hi}, index = {i})"
+        );
+        let (root, orig) = self.nodes.set_in_place(self.root, lo, hi, i, x);
+        self.root = root;
+        orig
+    }
+
+    pub fn prod(&self, range: impl RangeBounds<i64>) -> T {
+        let (l, r) = self.nodes.range(range);
+        self.nodes
+            .prod(self.root, self.nodes.lo, self.nodes.hi, l, r)
+    }
+
+    pub fn max_right(&self, l: i64, mut f: impl FnMut(&T) -> bool) -> (i64, T) {
+        let (l, _) = self.nodes.range(l..);
+        let mut acc = T::id();
+        let r = self
+            .nodes
+            .max_right(self.root, self.nodes.lo, self.nodes.hi, l, &mut f, &mut acc)
+            .unwrap_or(self.nodes.hi);
+        (r, acc)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Root(Idx);
+
+pub struct PersistentSegTree<T> {
+    nodes: Nodes<T>,
+}
+
+impl<T: Monoid> PersistentSegTree<T> {
+    pub fn new(lo: i64, hi: i64) -> Self {
+        Self {
+            nodes: Nodes::new(lo, hi),
+        }
+    }
+
+    pub fn empty(&self) -> Root {
+        Root(NIL)
+    }
+
+    pub fn count_node(&self) -> usize {
+        self.nodes.nodes.len()
+    }
+
+    pub fn get(&self, root: Root, i: i64) -> T {
+        self.nodes.get(root.0, i)
+    }
+
+    pub fn set(&mut self, root: Root, i: i64, x: T) -> Root {
+        let Nodes { lo, hi, .. } = self.nodes;
+        assert!(
+            lo <= i && i < hi,
+            "out of range (range = {lo}..{hi}, index = {i})"
+        );
+        Root(self.nodes.set_persistent(root.0, lo, hi, i, x))
+    }
+
+    pub fn prod(&self, root: Root, range: impl RangeBounds<i64>) -> T {
+        let (l, r) = self.nodes.range(range);
+        self.nodes.prod(root.0, self.nodes.lo, self.nodes.hi, l, r)
+    }
+
+    pub fn max_right(&self, root: Root, l: i64, mut f: impl FnMut(&T) -> bool) -> (i64, T) {
+        let (l, _) = self.nodes.range(l..);
+        let mut acc = T::id();
+        let r = self
+            .nodes
+            .max_right(root.0, self.nodes.lo, self.nodes.hi, l, &mut f, &mut acc)
+            .unwrap_or(self.nodes.hi);
+        (r, acc)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::trie::{Links, Node};
+
+#[derive(Clone)]
+pub struct Eertree {
+    s: Vec<u8>,
+    nodes: Vec<Node>,
+    link: Vec<usize>,
+    // The odd root (node 0) has length -1, stored as `!0` so that `len + 2`
+    // wraps to 1; `len()` reports it as `None`.
+    len: Vec<usize>,
+    occ: Vec<usize>,
+    last: usize,
+}
+
+impl Eertree {
+    pub fn new() -> Self {
+        Self {
+            s: vec![],
+            nodes: vec![Node::new(), Node::new()],
+            link: vec![0, 0],
+            len: vec![!0, 0],
+            occ: vec![0, 0],
+            last: 1,
+        }
+    }
+
+    pub fn count_node(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn distinct_palindromes(&self) -> usize {
+        self.nodes.len() - 2
+    }
+
+    pub fn last(&self) -> usize {
+        self.last
+    }
+
+    pub fn transition(&self, i: usize, c: u8) -> Option<usize> {
+        self.nodes[i].transition(c)
+    }
+
+    pub fn links(&self, i: usize) -> Links<'_> {
+        self.nodes[i].links()
+    }
+
+    pub fn link(&self, i: usize) -> usize {
+        self.link[i]
+    }
+
+    pub fn len(&self, i: usize) -> Option<usize> {
+        (i != 0).then(|| self.len[i])
+    }
+
+    fn fits(&self, v: usize, i: usize) -> bool {
+        let j = i.wrapping_sub(self.len[v].wrapping_add(1));
+        j < self.s.len() && self.s[j] == self.s[i]
+    }
+
+    pub fn push(&mut self, c: u8) -> usize {
+        let i = self.s.len();
+        self.s.push(c);
+        let mut v = self.last;
+        while !self.fits(v, i) {
+            v = self.link[v];
+        }
+        if let Some(u) = self.nodes[v].transition(c) {
+            self.occ[u] += 1;
+            self.last = u;
+            return u;
+        }
+        let len = self.len[v].wrapping_add(2);
+        let link = if len == 1 {
+            1
+        } else {
+            let mut w = self.link[v];
+            while !self.fits(w, i) {
+                w = self.link[w];
+            }
+            self.nodes[w].transition(c).unwrap()
+        };
+        let u = self.nodes.len();
+        self.nodes.push(Node::new());
+        self.link.push(link);
+        self.len.push(len);
+        self.occ.push(1);
+        self.nodes[v].set_transition(c, u);
+        self.last = u;
+        u
+    }
+
+    pub fn counts(&self) -> Vec<usize> {
+        let mut cnt = self.occ.clone();
+        for i in (2..self.nodes.len()).rev() {
+            cnt[self.link[i]] += cnt[i];
+        }
+        cnt[0] = 0;
+        cnt[1] = 0;
+        cnt
+    }
+}
+
+impl Default for Eertree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&[u8]> for Eertree {
+    fn from(s: &[u8]) -> Self {
+        let mut t = Self::new();
+        for &c in s {
+            t.push(c);
+        }
+        t
+    }
+}
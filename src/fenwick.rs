@@ -0,0 +1,276 @@
+use std::ops::{Bound, RangeBounds};
+
+pub trait Group {
+    fn id() -> Self;
+    fn op(&self, other: &Self) -> Self;
+    fn inv(&self) -> Self;
+}
+
+macro_rules! group {
+    ($ty:ident) => {
+        impl Group for $ty {
+            fn id() -> Self {
+                0
+            }
+            fn op(&self, other: &Self) -> Self {
+                self.wrapping_add(*other)
+            }
+            fn inv(&self) -> Self {
+                self.wrapping_neg()
+            }
+        }
+    };
+}
+
+group!(i32);
+group!(i64);
+group!(i128);
+group!(isize);
+group!(u32);
+group!(u64);
+group!(u128);
+group!(usize);
+
+#[derive(Clone, Debug)]
+pub struct Fenwick<T> {
+    a: Vec<T>,
+}
+
+impl<T: Group> Fenwick<T> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            a: (0..n).map(|_| T::id()).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    pub fn add(&mut self, i: usize, x: T) {
+        assert!(
+            i < self.len(),
+            "out of range (len = {}, index = {i})",
+            self.len()
+        );
+        let mut i = i + 1;
+        while i <= self.len() {
+            self.a[i - 1] = self.a[i - 1].op(&x);
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    pub fn prefix(&self, r: usize) -> T {
+        assert!(r <= self.len());
+        let mut r = r;
+        let mut x = T::id();
+        while r > 0 {
+            x = x.op(&self.a[r - 1]);
+            r &= r - 1;
+        }
+        x
+    }
+
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = range_to_pair(range, self.len());
+        self.prefix(l).inv().op(&self.prefix(r))
+    }
+
+    pub fn get(&self, i: usize) -> T {
+        self.sum(i..=i)
+    }
+
+    pub fn set(&mut self, i: usize, x: T) {
+        let d = self.get(i).inv().op(&x);
+        self.add(i, d);
+    }
+
+    pub fn lower_bound(&self, w: &T) -> usize
+    where
+        T: Ord,
+    {
+        if *w <= T::id() {
+            return 0;
+        }
+        let mut pos = 0;
+        let mut acc = T::id();
+        let mut step = self.len().next_power_of_two();
+        while step > 0 {
+            if pos + step <= self.len() {
+                let next = acc.op(&self.a[pos + step - 1]);
+                if next < *w {
+                    pos += step;
+                    acc = next;
+                }
+            }
+            step /= 2;
+        }
+        pos
+    }
+}
+
+impl<T: Group> From<Vec<T>> for Fenwick<T> {
+    fn from(mut a: Vec<T>) -> Self {
+        for i in 1..=a.len() {
+            let j = i + (i & i.wrapping_neg());
+            if j <= a.len() {
+                a[j - 1] = a[j - 1].op(&a[i - 1]);
+            }
+        }
+        Self { a }
+    }
+}
+
+fn range_to_pair(range: impl RangeBounds<usize>, n: usize) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Bound::Included(&l) => l,
+        Bound::Excluded(&l) => l + 1,
+        Bound::Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Bound::Included(&r) => r + 1,
+        Bound::Excluded(&r) => r,
+        Bound::Unbounded => n,
+    };
+    assert!(
+        l <= r && r <= n,
+        "out of range (len = {n}, l = {l}, r = {r})"
+    );
+    (l, r)
+}
+
+fn times<T: Group>(x: &T, mut k: usize) -> T {
+    let mut x = x.op(&T::id());
+    let mut y = T::id();
+    while k > 0 {
+        if k & 1 == 1 {
+            y = y.op(&x);
+        }
+        x = x.op(&x);
+        k >>= 1;
+    }
+    y
+}
+
+#[derive(Clone, Debug)]
+pub struct RangeAddFenwick<T> {
+    b0: Fenwick<T>,
+    b1: Fenwick<T>,
+}
+
+impl<T: Group> RangeAddFenwick<T> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            b0: Fenwick::new(n + 1),
+            b1: Fenwick::new(n + 1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.b0.len() - 1
+    }
+
+    pub fn add(&mut self, range: impl RangeBounds<usize>, x: T) {
+        let (l, r) = range_to_pair(range, self.len());
+        if l == r {
+            return;
+        }
+        let neg = x.inv();
+        self.b0.add(l, times(&neg, l));
+        self.b0.add(r, times(&x, r));
+        self.b1.add(l, x);
+        self.b1.add(r, neg);
+    }
+
+    pub fn prefix(&self, r: usize) -> T {
+        assert!(r <= self.len());
+        times(&self.b1.prefix(r), r).op(&self.b0.prefix(r))
+    }
+
+    pub fn sum(&self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = range_to_pair(range, self.len());
+        self.prefix(l).inv().op(&self.prefix(r))
+    }
+
+    pub fn get(&self, i: usize) -> T {
+        self.sum(i..=i)
+    }
+}
+
+impl<T: Group> From<Vec<T>> for RangeAddFenwick<T> {
+    fn from(a: Vec<T>) -> Self {
+        let n = a.len();
+        let mut b0 = Vec::with_capacity(n + 1);
+        b0.extend(a);
+        b0.push(T::id());
+        Self {
+            b0: b0.into(),
+            b1: Fenwick::new(n + 1),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FenwickMultiset {
+    fw: Fenwick<usize>,
+    len: usize,
+}
+
+impl FenwickMultiset {
+    pub fn new(n: usize) -> Self {
+        Self {
+            fw: Fenwick::new(n),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn insert(&mut self, x: usize) {
+        self.fw.add(x, 1);
+        self.len += 1;
+    }
+
+    pub fn remove_one(&mut self, x: usize) -> bool {
+        if self.count(x) == 0 {
+            return false;
+        }
+        self.fw.add(x, !0);
+        self.len -= 1;
+        true
+    }
+
+    pub fn count(&self, x: usize) -> usize {
+        self.fw.get(x)
+    }
+
+    pub fn count_lt(&self, x: usize) -> usize {
+        self.fw.prefix(x.min(self.fw.len()))
+    }
+
+    pub fn kth(&self, k: usize) -> Option<usize> {
+        if k < self.len {
+            Some(self.fw.lower_bound(&(k + 1)))
+        } else {
+            None
+        }
+    }
+}
+
+pub fn inversions<T: Ord>(a: &[T]) -> u64 {
+    let mut order: Vec<usize> = (0..a.len()).collect();
+    order.sort_by(|&i, &j| a[i].cmp(&a[j]));
+    let mut rank = vec![0; a.len()];
+    for k in 1..order.len() {
+        rank[order[k]] = rank[order[k - 1]] + (a[order[k - 1]] < a[order[k]]) as usize;
+    }
+    let mut set = FenwickMultiset::new(a.len());
+    let mut res = 0;
+    for &r in &rank {
+        res += (set.len() - set.count_lt(r + 1)) as u64;
+        set.insert(r);
+    }
+    res
+}
@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+
+use crate::{adj_list::*, matrix::*, modint::*, simple_rng::*};
+
+pub fn general_matching(g: &AdjList) -> Vec<usize> {
+    let n = g.num_vert();
+    let mut b = Blossom {
+        g,
+        mate: vec![!0; n],
+        p: vec![!0; n],
+        base: (0..n).collect(),
+        used: vec![false; n],
+        blossom: vec![false; n],
+        que: VecDeque::new(),
+    };
+    for u in 0..n {
+        if b.mate[u] != !0 {
+            continue;
+        }
+        if let Some(v) = g.adj(u).into_iter().find(|&v| v != u && b.mate[v] == !0) {
+            b.mate[u] = v;
+            b.mate[v] = u;
+        }
+    }
+    for u in 0..n {
+        if b.mate[u] != !0 {
+            continue;
+        }
+        if let Some(mut v) = b.find_path(u) {
+            while v != !0 {
+                let pv = b.p[v];
+                let ppv = b.mate[pv];
+                b.mate[v] = pv;
+                b.mate[pv] = v;
+                v = ppv;
+            }
+        }
+    }
+    b.mate
+}
+
+pub fn general_matching_size(g: &AdjList, rng: &mut Rng) -> usize {
+    const M: u32 = 998244353;
+    let n = g.num_vert();
+    let mut a = MatBuf::<ModInt<M>>::zeros(n, n);
+    for (u, v) in g.edges() {
+        if u < v {
+            let x = ModInt(rng.range(1..M));
+            a.set_elem(u, v, x);
+            a.set_elem(v, u, -x);
+        }
+    }
+    a.elimination();
+    let rank = (0..n)
+        .filter(|&i| a.row(i).iter().any(|&x| x != ModInt(0)))
+        .count();
+    rank / 2
+}
+
+struct Blossom<'a> {
+    g: &'a AdjList,
+    mate: Vec<usize>,
+    p: Vec<usize>,
+    base: Vec<usize>,
+    used: Vec<bool>,
+    blossom: Vec<bool>,
+    que: VecDeque<usize>,
+}
+
+impl Blossom<'_> {
+    fn lca(&self, mut a: usize, mut b: usize) -> usize {
+        let mut used = vec![false; self.mate.len()];
+        loop {
+            a = self.base[a];
+            used[a] = true;
+            if self.mate[a] == !0 {
+                break;
+            }
+            a = self.p[self.mate[a]];
+        }
+        loop {
+            b = self.base[b];
+            if used[b] {
+                return b;
+            }
+            b = self.p[self.mate[b]];
+        }
+    }
+
+    fn mark_path(&mut self, mut v: usize, b: usize, mut child: usize) {
+        while self.base[v] != b {
+            self.blossom[self.base[v]] = true;
+            self.blossom[self.base[self.mate[v]]] = true;
+            self.p[v] = child;
+            child = self.mate[v];
+            v = self.p[self.mate[v]];
+        }
+    }
+
+    fn find_path(&mut self, root: usize) -> Option<usize> {
+        let g = self.g;
+        self.used.fill(false);
+        self.p.fill(!0);
+        for (i, b) in self.base.iter_mut().enumerate() {
+            *b = i;
+        }
+        self.used[root] = true;
+        self.que.clear();
+        self.que.push_back(root);
+        while let Some(v) = self.que.pop_front() {
+            for to in g.adj(v) {
+                if self.base[v] == self.base[to] || self.mate[v] == to {
+                    continue;
+                }
+                if to == root || self.mate[to] != !0 && self.p[self.mate[to]] != !0 {
+                    let cur = self.lca(v, to);
+                    self.blossom.fill(false);
+                    self.mark_path(v, cur, to);
+                    self.mark_path(to, cur, v);
+                    for i in 0..self.base.len() {
+                        if self.blossom[self.base[i]] {
+                            self.base[i] = cur;
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                self.que.push_back(i);
+                            }
+                        }
+                    }
+                } else if self.p[to] == !0 {
+                    self.p[to] = v;
+                    let w = self.mate[to];
+                    if w == !0 {
+                        return Some(to);
+                    }
+                    self.used[w] = true;
+                    self.que.push_back(w);
+                }
+            }
+        }
+        None
+    }
+}
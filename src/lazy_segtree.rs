@@ -155,7 +155,7 @@ impl<T: Monoid, M: Map<T>> LazySegTree<T, M> {
         }
     }
 
-    pub fn get(&self, i: usize) {
+    pub fn get(&self, i: usize) -> T {
         let mut i = self.node_index(i);
         let mut x = self.value[i].op(&T::id());
         i /= 2;
@@ -163,6 +163,109 @@ impl<T: Monoid, M: Map<T>> LazySegTree<T, M> {
             x = self.map[i].map(&x);
             i /= 2;
         }
+        x
+    }
+
+    pub fn apply_at(&mut self, i: usize, map: M) {
+        self.update(i, |x| *x = map.map(x));
+    }
+
+    pub fn update(&mut self, i: usize, f: impl FnOnce(&mut T)) {
+        let mut i = self.node_index(i);
+        for e in (1..=i.ilog2()).rev() {
+            self.propagate(i >> e);
+        }
+        f(&mut self.value[i]);
+        i /= 2;
+        while i >= 1 {
+            self.value[i] = self.value[2 * i].op(&self.value[2 * i + 1]);
+            i /= 2;
+        }
+    }
+
+    pub fn max_right(&mut self, l: usize, mut f: impl FnMut(&T) -> bool) -> (usize, T) {
+        assert!(l <= self.len());
+        let mut prod = T::id();
+        if l == self.len() {
+            return (l, prod);
+        }
+        let mut r = self.node_index(l);
+        for e in (1..=r.ilog2()).rev() {
+            self.propagate(r >> e);
+        }
+        loop {
+            r >>= r.trailing_zeros();
+            loop {
+                let prod_new = prod.op(&self.value[r]);
+                if f(&prod_new) {
+                    prod = prod_new;
+                    r += 1;
+                    if r.count_ones() <= 1 {
+                        return (self.len(), prod);
+                    }
+                    break;
+                }
+                if r >= self.len() {
+                    let r = if r >= self.len().next_power_of_two() {
+                        r - self.len().next_power_of_two()
+                    } else {
+                        r + self.len() - self.len().next_power_of_two()
+                    };
+                    return (r, prod);
+                }
+                self.propagate(r);
+                r *= 2;
+            }
+        }
+    }
+
+    pub fn min_left(&mut self, r: usize, mut f: impl FnMut(&T) -> bool) -> (usize, T) {
+        assert!(r <= self.len());
+        let mut prod = T::id();
+        if r == 0 {
+            return (0, prod);
+        }
+        let i = self.node_index(r - 1);
+        for e in (1..=i.ilog2()).rev() {
+            self.propagate(i >> e);
+        }
+        let mut l = self.node_index(r);
+        loop {
+            l = (l >> l.trailing_zeros()).max(2);
+            loop {
+                let prod_new = self.value[l - 1].op(&prod);
+                if f(&prod_new) {
+                    prod = prod_new;
+                    l -= 1;
+                    if l.count_ones() <= 1 {
+                        return (0, prod);
+                    }
+                    break;
+                }
+                if l > self.len() {
+                    let l = if l > self.len().next_power_of_two() {
+                        l - self.len().next_power_of_two()
+                    } else {
+                        l + self.len() - self.len().next_power_of_two()
+                    };
+                    return (l, prod);
+                }
+                self.propagate(l - 1);
+                l *= 2;
+            }
+        }
+    }
+
+    pub fn to_vec(&mut self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        for i in 1..self.map.len() {
+            self.propagate(i);
+        }
+        (0..self.len())
+            .map(|i| self.value[self.node_index(i)].clone())
+            .collect()
     }
 
     pub fn set(&mut self, i: usize, value: T) -> T {
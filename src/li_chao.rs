@@ -0,0 +1,123 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::cht::{Line, Scalar};
+
+#[derive(Clone, Debug)]
+pub struct LiChaoTree<T> {
+    xs: Vec<T>,
+    n: usize,
+    size: usize,
+    lines: Vec<Option<Line<T>>>,
+}
+
+impl<T: Scalar + Ord> LiChaoTree<T> {
+    pub fn new(mut xs: Vec<T>) -> Self {
+        xs.sort();
+        xs.dedup();
+        let n = xs.len();
+        let size = n.next_power_of_two();
+        if let Some(&last) = xs.last() {
+            xs.resize(size, last);
+        }
+        Self {
+            xs,
+            n,
+            size,
+            lines: vec![None; 2 * size],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn xs(&self) -> &[T] {
+        &self.xs[..self.n]
+    }
+
+    pub fn add_line(&mut self, line: Line<T>) {
+        if self.n > 0 {
+            self.add_at(1, line);
+        }
+    }
+
+    pub fn add_segment(&mut self, range: impl RangeBounds<T>, line: Line<T>) {
+        let xs = &self.xs[..self.n];
+        let l = match range.start_bound() {
+            Bound::Included(l) => xs.partition_point(|x| x < l),
+            Bound::Excluded(l) => xs.partition_point(|x| x <= l),
+            Bound::Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Bound::Included(r) => xs.partition_point(|x| x <= r),
+            Bound::Excluded(r) => xs.partition_point(|x| x < r),
+            Bound::Unbounded => self.n,
+        };
+        if l >= r {
+            return;
+        }
+        let (mut l, mut r) = (l + self.size, r + self.size);
+        while l < r {
+            if l & 1 == 1 {
+                self.add_at(l, line);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                self.add_at(r, line);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+    }
+
+    pub fn y_min(&self, x: T) -> Option<T> {
+        let i = self
+            .xs()
+            .binary_search(&x)
+            .expect("x is not in the coordinate set");
+        let mut k = i + self.size;
+        let mut res: Option<T> = None;
+        while k > 0 {
+            if let Some(line) = &self.lines[k] {
+                let y = line.y(x);
+                if res.is_none_or(|r| y < r) {
+                    res = Some(y);
+                }
+            }
+            k >>= 1;
+        }
+        res
+    }
+
+    fn add_at(&mut self, mut k: usize, mut line: Line<T>) {
+        let depth = k.ilog2();
+        let width = self.size >> depth;
+        let mut l = (k - (1 << depth)) * width;
+        let mut r = l + width;
+        loop {
+            let Some(cur) = &mut self.lines[k] else {
+                self.lines[k] = Some(line);
+                return;
+            };
+            let m = (l + r) / 2;
+            let xm = self.xs[m];
+            if line.y(xm) < cur.y(xm) {
+                std::mem::swap(cur, &mut line);
+            }
+            if r - l == 1 {
+                return;
+            }
+            let (xl, xr) = (self.xs[l], self.xs[r - 1]);
+            if line.y(xl) < cur.y(xl) {
+                k *= 2;
+                r = m;
+            } else if line.y(xr) < cur.y(xr) {
+                k = 2 * k + 1;
+                l = m;
+            } else {
+                return;
+            }
+        }
+    }
+}
@@ -3,26 +3,40 @@
 pub mod adj_list;
 pub mod aho_corasick;
 pub mod bigint;
+pub mod binary_trie;
 pub mod bipartite_matching;
 pub mod bitset;
 pub mod cartesian_tree;
 pub mod cht;
 pub mod dsu;
+pub mod dynamic_segtree;
+pub mod eertree;
 pub mod factorize;
+pub mod fenwick;
 pub mod floor_sum;
+pub mod general_matching;
 pub mod integer;
 pub mod lazy_segtree;
+pub mod li_chao;
 pub mod matrix;
 pub mod max_frow;
 pub mod modint;
+pub mod monoids;
 pub mod montgomery;
 pub mod poly;
 pub mod scc;
 pub mod segtree;
+pub mod segtree_2d;
+pub mod segtree_beats;
 pub mod simple_rng;
+pub mod sparse_table;
+pub mod string;
 pub mod suffix_array;
+pub mod suffix_automaton;
+pub mod treap;
 pub mod trie;
 pub mod two_sat;
+pub mod wavelet_matrix;
 
 #[cfg(test)]
 mod tests;
@@ -5,6 +5,8 @@ use std::{
     ptr, slice,
 };
 
+use crate::modint::ModInt;
+
 pub trait Scalar:
     Copy + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
 {
@@ -30,6 +32,11 @@ prim!(i128);
 prim!(f32);
 prim!(f64);
 
+impl<const M: u32> Scalar for ModInt<M> {
+    const ZERO: Self = ModInt(0);
+    const ONE: Self = ModInt(1);
+}
+
 pub trait Vector<T: Scalar> {
     fn size(&self) -> usize;
     fn elem(&self, i: usize) -> T;
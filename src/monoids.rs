@@ -0,0 +1,510 @@
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Not};
+
+use crate::{lazy_segtree::Map, modint::ModInt, segtree, sparse_table::Idempotent};
+
+pub trait Num: Copy + PartialEq + Add<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+pub trait Bounded: Copy + Ord {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+pub trait Int:
+    Num
+    + Bounded
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+{
+    fn gcd(self, other: Self) -> Self;
+}
+
+macro_rules! int {
+    ($ty:ident, $abs:expr) => {
+        impl Num for $ty {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        }
+        impl Bounded for $ty {
+            const MIN: Self = $ty::MIN;
+            const MAX: Self = $ty::MAX;
+        }
+        impl Int for $ty {
+            fn gcd(self, other: Self) -> Self {
+                // Computed on absolute values so that `MIN` does not overflow. The
+                // result 2^(N-1), from `MIN` with `0` or `MIN`, is not representable.
+                let (mut x, mut y) = ($abs(self), $abs(other));
+                while y != 0 {
+                    (x, y) = (y, x % y);
+                }
+                <$ty>::try_from(x).expect("gcd overflow")
+            }
+        }
+    };
+}
+
+int!(i8, i8::unsigned_abs);
+int!(i16, i16::unsigned_abs);
+int!(i32, i32::unsigned_abs);
+int!(i64, i64::unsigned_abs);
+int!(i128, i128::unsigned_abs);
+int!(isize, isize::unsigned_abs);
+int!(u8, std::convert::identity);
+int!(u16, std::convert::identity);
+int!(u32, std::convert::identity);
+int!(u64, std::convert::identity);
+int!(u128, std::convert::identity);
+int!(usize, std::convert::identity);
+
+impl<const M: u32> Num for ModInt<M> {
+    const ZERO: Self = ModInt(0);
+    const ONE: Self = ModInt(1);
+}
+
+macro_rules! lazy_monoid {
+    ($ty:ident, $($bound:tt)+) => {
+        impl<T: $($bound)+> crate::lazy_segtree::Monoid for $ty<T> {
+            fn id() -> Self {
+                <Self as segtree::Monoid>::id()
+            }
+            fn op(&self, other: &Self) -> Self {
+                segtree::Monoid::op(self, other)
+            }
+        }
+    };
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Sum<T>(pub T);
+
+impl<T: Num> segtree::Monoid for Sum<T> {
+    fn id() -> Self {
+        Sum(T::ZERO)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Sum(self.0 + other.0)
+    }
+}
+lazy_monoid!(Sum, Num);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SumLen<T> {
+    pub sum: T,
+    pub len: T,
+}
+
+impl<T: Num> SumLen<T> {
+    pub fn new(x: T) -> Self {
+        Self {
+            sum: x,
+            len: T::ONE,
+        }
+    }
+}
+
+impl<T: Num> segtree::Monoid for SumLen<T> {
+    fn id() -> Self {
+        Self {
+            sum: T::ZERO,
+            len: T::ZERO,
+        }
+    }
+    fn op(&self, other: &Self) -> Self {
+        Self {
+            sum: self.sum + other.sum,
+            len: self.len + other.len,
+        }
+    }
+}
+lazy_monoid!(SumLen, Num);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Min<T>(pub T);
+
+impl<T: Bounded> segtree::Monoid for Min<T> {
+    fn id() -> Self {
+        Min(T::MAX)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Min(self.0.min(other.0))
+    }
+}
+lazy_monoid!(Min, Bounded);
+impl<T: Bounded> Idempotent for Min<T> {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Max<T>(pub T);
+
+impl<T: Bounded> segtree::Monoid for Max<T> {
+    fn id() -> Self {
+        Max(T::MIN)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Max(self.0.max(other.0))
+    }
+}
+lazy_monoid!(Max, Bounded);
+impl<T: Bounded> Idempotent for Max<T> {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Gcd<T>(pub T);
+
+impl<T: Int> segtree::Monoid for Gcd<T> {
+    fn id() -> Self {
+        Gcd(T::ZERO)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Gcd(self.0.gcd(other.0))
+    }
+}
+lazy_monoid!(Gcd, Int);
+impl<T: Int> Idempotent for Gcd<T> {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Xor<T>(pub T);
+
+impl<T: Int> segtree::Monoid for Xor<T> {
+    fn id() -> Self {
+        Xor(T::ZERO)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Xor(self.0 ^ other.0)
+    }
+}
+lazy_monoid!(Xor, Int);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct And<T>(pub T);
+
+impl<T: Int> segtree::Monoid for And<T> {
+    fn id() -> Self {
+        And(!T::ZERO)
+    }
+    fn op(&self, other: &Self) -> Self {
+        And(self.0 & other.0)
+    }
+}
+lazy_monoid!(And, Int);
+impl<T: Int> Idempotent for And<T> {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Or<T>(pub T);
+
+impl<T: Int> segtree::Monoid for Or<T> {
+    fn id() -> Self {
+        Or(T::ZERO)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Or(self.0 | other.0)
+    }
+}
+lazy_monoid!(Or, Int);
+impl<T: Int> Idempotent for Or<T> {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Affine<T> {
+    pub a: T,
+    pub b: T,
+}
+
+impl<T: Num> Affine<T> {
+    pub fn new(a: T, b: T) -> Self {
+        Self { a, b }
+    }
+
+    pub fn apply(&self, x: T) -> T {
+        self.a * x + self.b
+    }
+}
+
+impl<T: Num> segtree::Monoid for Affine<T> {
+    fn id() -> Self {
+        Self::new(T::ONE, T::ZERO)
+    }
+    fn op(&self, other: &Self) -> Self {
+        Self::new(other.a * self.a, other.a * self.b + other.b)
+    }
+}
+lazy_monoid!(Affine, Num);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MaxSubarray<T> {
+    pub sum: T,
+    pub prefix: T,
+    pub suffix: T,
+    pub best: T,
+}
+
+impl<T: Num + Bounded> MaxSubarray<T> {
+    pub fn new(x: T) -> Self {
+        Self {
+            sum: x,
+            prefix: x,
+            suffix: x,
+            best: x,
+        }
+    }
+}
+
+fn add_or_min<T: Num + Bounded>(x: T, y: T) -> T {
+    if x == T::MIN || y == T::MIN {
+        T::MIN
+    } else {
+        x + y
+    }
+}
+
+impl<T: Num + Bounded> segtree::Monoid for MaxSubarray<T> {
+    fn id() -> Self {
+        Self {
+            sum: T::ZERO,
+            prefix: T::MIN,
+            suffix: T::MIN,
+            best: T::MIN,
+        }
+    }
+    fn op(&self, other: &Self) -> Self {
+        Self {
+            sum: self.sum + other.sum,
+            prefix: self.prefix.max(add_or_min(self.sum, other.prefix)),
+            suffix: other.suffix.max(add_or_min(self.suffix, other.sum)),
+            best: self
+                .best
+                .max(other.best)
+                .max(add_or_min(self.suffix, other.prefix)),
+        }
+    }
+}
+lazy_monoid!(MaxSubarray, Num + Bounded);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ArgMin<T> {
+    pub value: T,
+    pub index: usize,
+}
+
+impl<T: Bounded> segtree::Monoid for ArgMin<T> {
+    fn id() -> Self {
+        Self {
+            value: T::MAX,
+            index: !0,
+        }
+    }
+    fn op(&self, other: &Self) -> Self {
+        if (other.value, other.index) < (self.value, self.index) {
+            *other
+        } else {
+            *self
+        }
+    }
+}
+lazy_monoid!(ArgMin, Bounded);
+impl<T: Bounded> Idempotent for ArgMin<T> {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ArgMax<T> {
+    pub value: T,
+    pub index: usize,
+}
+
+impl<T: Bounded> segtree::Monoid for ArgMax<T> {
+    fn id() -> Self {
+        Self {
+            value: T::MIN,
+            index: !0,
+        }
+    }
+    fn op(&self, other: &Self) -> Self {
+        if other.value > self.value || other.value == self.value && other.index < self.index {
+            *other
+        } else {
+            *self
+        }
+    }
+}
+lazy_monoid!(ArgMax, Bounded);
+impl<T: Bounded> Idempotent for ArgMax<T> {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RangeAdd<T>(pub T);
+
+impl<T: Num> Map<SumLen<T>> for RangeAdd<T> {
+    fn id() -> Self {
+        RangeAdd(T::ZERO)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        RangeAdd(self.0 + other.0)
+    }
+    fn map(&self, x: &SumLen<T>) -> SumLen<T> {
+        SumLen {
+            sum: x.sum + self.0 * x.len,
+            len: x.len,
+        }
+    }
+}
+
+impl<T: Num + Bounded> Map<Min<T>> for RangeAdd<T> {
+    fn id() -> Self {
+        RangeAdd(T::ZERO)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        RangeAdd(self.0 + other.0)
+    }
+    fn map(&self, x: &Min<T>) -> Min<T> {
+        if x.0 == T::MAX {
+            *x
+        } else {
+            Min(x.0 + self.0)
+        }
+    }
+}
+
+impl<T: Num + Bounded> Map<Max<T>> for RangeAdd<T> {
+    fn id() -> Self {
+        RangeAdd(T::ZERO)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        RangeAdd(self.0 + other.0)
+    }
+    fn map(&self, x: &Max<T>) -> Max<T> {
+        if x.0 == T::MIN {
+            *x
+        } else {
+            Max(x.0 + self.0)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RangeAssign<T>(pub Option<T>);
+
+impl<T: Num> Map<SumLen<T>> for RangeAssign<T> {
+    fn id() -> Self {
+        RangeAssign(None)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        RangeAssign(self.0.or(other.0))
+    }
+    fn map(&self, x: &SumLen<T>) -> SumLen<T> {
+        match self.0 {
+            Some(c) => SumLen {
+                sum: c * x.len,
+                len: x.len,
+            },
+            None => *x,
+        }
+    }
+}
+
+impl<T: Bounded> Map<Min<T>> for RangeAssign<T> {
+    fn id() -> Self {
+        RangeAssign(None)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        RangeAssign(self.0.or(other.0))
+    }
+    fn map(&self, x: &Min<T>) -> Min<T> {
+        if x.0 == T::MAX {
+            *x
+        } else {
+            Min(self.0.unwrap_or(x.0))
+        }
+    }
+}
+
+impl<T: Bounded> Map<Max<T>> for RangeAssign<T> {
+    fn id() -> Self {
+        RangeAssign(None)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        RangeAssign(self.0.or(other.0))
+    }
+    fn map(&self, x: &Max<T>) -> Max<T> {
+        if x.0 == T::MIN {
+            *x
+        } else {
+            Max(self.0.unwrap_or(x.0))
+        }
+    }
+}
+
+impl<T: Num> Map<SumLen<T>> for Affine<T> {
+    fn id() -> Self {
+        Self::new(T::ONE, T::ZERO)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        Self::new(self.a * other.a, self.a * other.b + self.b)
+    }
+    fn map(&self, x: &SumLen<T>) -> SumLen<T> {
+        SumLen {
+            sum: self.a * x.sum + self.b * x.len,
+            len: x.len,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Chmin<T>(pub T);
+
+impl<T: Bounded> Map<Min<T>> for Chmin<T> {
+    fn id() -> Self {
+        Chmin(T::MAX)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        Chmin(self.0.min(other.0))
+    }
+    fn map(&self, x: &Min<T>) -> Min<T> {
+        if x.0 == T::MAX {
+            *x
+        } else {
+            Min(x.0.min(self.0))
+        }
+    }
+}
+
+impl<T: Bounded> Map<Max<T>> for Chmin<T> {
+    fn id() -> Self {
+        Chmin(T::MAX)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        Chmin(self.0.min(other.0))
+    }
+    fn map(&self, x: &Max<T>) -> Max<T> {
+        Max(x.0.min(self.0))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Chmax<T>(pub T);
+
+impl<T: Bounded> Map<Min<T>> for Chmax<T> {
+    fn id() -> Self {
+        Chmax(T::MIN)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        Chmax(self.0.max(other.0))
+    }
+    fn map(&self, x: &Min<T>) -> Min<T> {
+        Min(x.0.max(self.0))
+    }
+}
+
+impl<T: Bounded> Map<Max<T>> for Chmax<T> {
+    fn id() -> Self {
+        Chmax(T::MIN)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        Chmax(self.0.max(other.0))
+    }
+    fn map(&self, x: &Max<T>) -> Max<T> {
+        if x.0 == T::MIN {
+            *x
+        } else {
+            Max(x.0.max(self.0))
+        }
+    }
+}
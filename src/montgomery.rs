@@ -1,3 +1,4 @@
+#[derive(Clone, Copy, Debug)]
 pub struct Montgomery<T> {
     pub n: T,
     pub ninv: T,
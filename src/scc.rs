@@ -38,7 +38,6 @@ impl<F: FnMut(usize) -> A, A: IntoIterator<Item = usize>> Dfs<F> {
                 self.low[u] = (self.low[u] as isize).min(self.ord[v] as isize) as usize;
             }
         }
-        dbg!(u, &self.ord, &self.low);
         if self.ord[u] == self.low[u] {
             let i = self.stack.iter().rposition(|&v| v == u).unwrap();
             for &v in &self.stack[i..] {
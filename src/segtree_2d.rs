@@ -0,0 +1,303 @@
+use std::{
+    mem,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::segtree::{Monoid, SegTree};
+
+fn lower<K>(keys: &[K], bound: Bound<&i64>, key: impl Fn(&K) -> i64) -> usize {
+    match bound {
+        Bound::Included(&l) => keys.partition_point(|k| key(k) < l),
+        Bound::Excluded(&l) => keys.partition_point(|k| key(k) <= l),
+        Bound::Unbounded => 0,
+    }
+}
+
+fn upper<K>(keys: &[K], bound: Bound<&i64>, key: impl Fn(&K) -> i64) -> usize {
+    match bound {
+        Bound::Included(&r) => keys.partition_point(|k| key(k) <= r),
+        Bound::Excluded(&r) => keys.partition_point(|k| key(k) < r),
+        Bound::Unbounded => keys.len(),
+    }
+}
+
+pub struct RangeTree2d<T> {
+    xs: Vec<i64>,
+    pos: Vec<usize>,
+    ys: Vec<Vec<(i64, usize)>>,
+    seg: Vec<SegTree<T>>,
+}
+
+impl<T: Monoid> RangeTree2d<T> {
+    pub fn new(points: &[(i64, i64)]) -> Self {
+        points
+            .iter()
+            .map(|&(x, y)| (x, y, T::id()))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pos.len()
+    }
+
+    pub fn get(&self, i: usize) -> &T {
+        let p = self.pos[i];
+        &self.seg[p + self.xs.len().next_power_of_two()][0]
+    }
+
+    pub fn set(&mut self, i: usize, x: T) -> T {
+        assert!(
+            i < self.len(),
+            "out of range (len = {}, index = {i})",
+            self.len()
+        );
+        let p = self.pos[i];
+        let mut k = p + self.xs.len().next_power_of_two();
+        let orig = self.seg[k].set(0, x.op(&T::id()));
+        let y = self.ys[k][0].0;
+        while k > 1 {
+            k /= 2;
+            let j = self.ys[k].binary_search(&(y, p)).unwrap();
+            self.seg[k].set(j, x.op(&T::id()));
+        }
+        orig
+    }
+
+    pub fn prod(&self, xs: impl RangeBounds<i64>, ys: impl RangeBounds<i64>) -> T {
+        let mut l = lower(&self.xs, xs.start_bound(), |&x| x);
+        let mut r = upper(&self.xs, xs.end_bound(), |&x| x);
+        if l >= r {
+            return T::id();
+        }
+        let prod_node = |k: usize| {
+            let l = lower(&self.ys[k], ys.start_bound(), |&(y, _)| y);
+            let r = upper(&self.ys[k], ys.end_bound(), |&(y, _)| y);
+            if l < r {
+                self.seg[k].prod(l..r)
+            } else {
+                T::id()
+            }
+        };
+        let size = self.xs.len().next_power_of_two();
+        l += size;
+        r += size;
+        let mut x = T::id();
+        let mut y = T::id();
+        while l < r {
+            if l % 2 == 1 {
+                x = x.op(&prod_node(l));
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                y = prod_node(r).op(&y);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        x.op(&y)
+    }
+}
+
+impl<T: Monoid> From<Vec<(i64, i64, T)>> for RangeTree2d<T> {
+    fn from(points: Vec<(i64, i64, T)>) -> Self {
+        let n = points.len();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by_key(|&i| (points[i].0, points[i].1));
+        let mut pos = vec![0; n];
+        for (p, &i) in order.iter().enumerate() {
+            pos[i] = p;
+        }
+        let xs = order.iter().map(|&i| points[i].0).collect();
+
+        let size = n.next_power_of_two();
+        let mut ys = vec![vec![]; 2 * size];
+        for (p, &i) in order.iter().enumerate() {
+            ys[size + p].push((points[i].1, p));
+        }
+        for k in (1..size).rev() {
+            let mut merged = Vec::with_capacity(ys[2 * k].len() + ys[2 * k + 1].len());
+            let (a, b) = (&ys[2 * k], &ys[2 * k + 1]);
+            let (mut i, mut j) = (0, 0);
+            while i < a.len() || j < b.len() {
+                if j == b.len() || i < a.len() && a[i] < b[j] {
+                    merged.push(a[i]);
+                    i += 1;
+                } else {
+                    merged.push(b[j]);
+                    j += 1;
+                }
+            }
+            ys[k] = merged;
+        }
+
+        let values: Vec<T> = order.iter().map(|&i| points[i].2.op(&T::id())).collect();
+        let seg = ys
+            .iter()
+            .map(|ys| {
+                if ys.is_empty() {
+                    SegTree::new(0)
+                } else {
+                    ys.iter()
+                        .map(|&(_, p)| values[p].op(&T::id()))
+                        .collect::<Vec<_>>()
+                        .into()
+                }
+            })
+            .collect();
+        Self { xs, pos, ys, seg }
+    }
+}
+
+pub struct SegTree2d<T> {
+    h: usize,
+    w: usize,
+    a: Vec<T>,
+}
+
+impl<T: Monoid> SegTree2d<T> {
+    pub fn new(h: usize, w: usize) -> Self {
+        Self {
+            h,
+            w,
+            a: (0..4 * h * w).map(|_| T::id()).collect(),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    #[inline]
+    fn idx(&self, i: usize, j: usize) -> usize {
+        i * 2 * self.w + j
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> &T {
+        assert!(i < self.h && j < self.w);
+        &self.a[self.idx(self.h + i, self.w + j)]
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, x: T) -> T {
+        assert!(
+            i < self.h && j < self.w,
+            "out of range (size = {}x{}, index = ({i}, {j}))",
+            self.h,
+            self.w
+        );
+        let (i, j) = (self.h + i, self.w + j);
+        let k = self.idx(i, j);
+        let orig = mem::replace(&mut self.a[k], x);
+        self.pull_row(i, j);
+        let mut i = i / 2;
+        while i >= 1 {
+            let mut j = j;
+            while j >= 1 {
+                let k = self.idx(i, j);
+                self.a[k] = self.a[self.idx(2 * i, j)].op(&self.a[self.idx(2 * i + 1, j)]);
+                j /= 2;
+            }
+            i /= 2;
+        }
+        orig
+    }
+
+    fn pull_row(&mut self, i: usize, mut j: usize) {
+        j /= 2;
+        while j >= 1 {
+            let k = self.idx(i, j);
+            self.a[k] = self.a[self.idx(i, 2 * j)].op(&self.a[self.idx(i, 2 * j + 1)]);
+            j /= 2;
+        }
+    }
+
+    pub fn prod(&self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> T {
+        let (mut t, mut b) = range(rows, self.h);
+        let (l, r) = range(cols, self.w);
+        t += self.h;
+        b += self.h;
+        let mut x = T::id();
+        let mut y = T::id();
+        while t < b {
+            if t % 2 == 1 {
+                x = x.op(&self.prod_row(t, l, r));
+                t += 1;
+            }
+            if b % 2 == 1 {
+                b -= 1;
+                y = self.prod_row(b, l, r).op(&y);
+            }
+            t /= 2;
+            b /= 2;
+        }
+        x.op(&y)
+    }
+
+    fn prod_row(&self, i: usize, l: usize, r: usize) -> T {
+        let (mut l, mut r) = (l + self.w, r + self.w);
+        let mut x = T::id();
+        let mut y = T::id();
+        while l < r {
+            if l % 2 == 1 {
+                x = x.op(&self.a[self.idx(i, l)]);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                y = self.a[self.idx(i, r)].op(&y);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        x.op(&y)
+    }
+}
+
+impl<T: Monoid> From<Vec<Vec<T>>> for SegTree2d<T> {
+    fn from(a: Vec<Vec<T>>) -> Self {
+        let h = a.len();
+        let w = a.first().map_or(0, |row| row.len());
+        let mut st = Self::new(h, w);
+        for (i, row) in a.into_iter().enumerate() {
+            assert_eq!(row.len(), w, "rows must have the same length");
+            for (j, x) in row.into_iter().enumerate() {
+                let k = st.idx(h + i, w + j);
+                st.a[k] = x;
+            }
+            for j in (1..w).rev() {
+                let k = st.idx(h + i, j);
+                st.a[k] = st.a[st.idx(h + i, 2 * j)].op(&st.a[st.idx(h + i, 2 * j + 1)]);
+            }
+        }
+        for i in (1..h).rev() {
+            for j in 1..2 * w {
+                let k = st.idx(i, j);
+                st.a[k] = st.a[st.idx(2 * i, j)].op(&st.a[st.idx(2 * i + 1, j)]);
+            }
+        }
+        st
+    }
+}
+
+fn range(range: impl RangeBounds<usize>, n: usize) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Bound::Included(&l) => l,
+        Bound::Excluded(&l) => l + 1,
+        Bound::Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Bound::Included(&r) => r + 1,
+        Bound::Excluded(&r) => r,
+        Bound::Unbounded => n,
+    };
+    assert!(
+        l <= r && r <= n,
+        "out of range (len = {n}, l = {l}, r = {r})"
+    );
+    (l, r)
+}
@@ -0,0 +1,343 @@
+use std::{
+    cmp::Ordering,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::lazy_segtree::Monoid;
+
+pub trait BeatsMap<T> {
+    fn id() -> Self;
+    fn comp(&self, other: &Self) -> Self;
+    fn map(&self, x: &T) -> Option<T>;
+    fn is_break(&self, _x: &T) -> bool {
+        false
+    }
+}
+
+pub struct SegTreeBeats<T, M> {
+    n: usize,
+    value: Vec<T>,
+    map: Vec<M>,
+}
+
+impl<T: Monoid, M: BeatsMap<T>> SegTreeBeats<T, M> {
+    pub fn new(n: usize) -> Self {
+        (0..n).map(|_| T::id()).collect::<Vec<_>>().into()
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    fn size(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn prod(&mut self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = self.range(range);
+        self.prod_rec(1, 0, self.size(), l, r)
+    }
+
+    fn prod_rec(&mut self, k: usize, lo: usize, hi: usize, l: usize, r: usize) -> T {
+        if r <= lo || hi <= l {
+            return T::id();
+        }
+        if l <= lo && hi <= r {
+            return self.value[k].op(&T::id());
+        }
+        self.propagate(k);
+        let mid = (lo + hi) / 2;
+        self.prod_rec(2 * k, lo, mid, l, r)
+            .op(&self.prod_rec(2 * k + 1, mid, hi, l, r))
+    }
+
+    pub fn apply(&mut self, range: impl RangeBounds<usize>, map: M) {
+        let (l, r) = self.range(range);
+        self.apply_rec(1, 0, self.size(), l, r, &map);
+    }
+
+    fn apply_rec(&mut self, k: usize, lo: usize, hi: usize, l: usize, r: usize, map: &M) {
+        if r <= lo || hi <= l || map.is_break(&self.value[k]) {
+            return;
+        }
+        if l <= lo && hi <= r {
+            if let Some(value) = map.map(&self.value[k]) {
+                self.value[k] = value;
+                if k < self.size() {
+                    self.map[k] = map.comp(&self.map[k]);
+                }
+                return;
+            }
+            assert!(k < self.size(), "map failed on a leaf");
+        }
+        self.propagate(k);
+        let mid = (lo + hi) / 2;
+        self.apply_rec(2 * k, lo, mid, l, r, map);
+        self.apply_rec(2 * k + 1, mid, hi, l, r, map);
+        self.value[k] = self.value[2 * k].op(&self.value[2 * k + 1]);
+    }
+
+    pub fn get(&mut self, i: usize) -> T {
+        self.prod(i..=i)
+    }
+
+    pub fn set(&mut self, i: usize, value: T) {
+        assert!(i < self.n, "out of range (len = {}, index = {i})", self.n);
+        let mut k = i + self.size();
+        for e in (1..=self.size().trailing_zeros()).rev() {
+            self.propagate(k >> e);
+        }
+        self.value[k] = value;
+        k /= 2;
+        while k >= 1 {
+            self.value[k] = self.value[2 * k].op(&self.value[2 * k + 1]);
+            k /= 2;
+        }
+    }
+
+    #[inline]
+    fn propagate(&mut self, k: usize) {
+        for c in [2 * k, 2 * k + 1] {
+            self.value[c] = self.map[k]
+                .map(&self.value[c])
+                .expect("pending map must be applicable to children");
+            if c < self.size() {
+                self.map[c] = self.map[k].comp(&self.map[c]);
+            }
+        }
+        self.map[k] = M::id();
+    }
+
+    #[inline]
+    fn range(&self, range: impl RangeBounds<usize>) -> (usize, usize) {
+        let l = match range.start_bound() {
+            Bound::Included(&l) => l,
+            Bound::Excluded(&l) => l + 1,
+            Bound::Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Bound::Included(&r) => r + 1,
+            Bound::Excluded(&r) => r,
+            Bound::Unbounded => self.n,
+        };
+        assert!(l <= r && r <= self.n);
+        (l, r)
+    }
+}
+
+impl<T: Monoid, M: BeatsMap<T>> From<Vec<T>> for SegTreeBeats<T, M> {
+    fn from(a: Vec<T>) -> Self {
+        let n = a.len();
+        let size = n.next_power_of_two();
+        let mut value: Vec<T> = (0..size).map(|_| T::id()).collect();
+        value.extend(a);
+        value.extend((n..size).map(|_| T::id()));
+        for k in (1..size).rev() {
+            value[k] = value[2 * k].op(&value[2 * k + 1]);
+        }
+        let map = (0..size).map(|_| M::id()).collect();
+        Self { n, value, map }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChminChmaxSum {
+    pub sum: i64,
+    pub len: i64,
+    pub max: i64,
+    pub min: i64,
+    max2: i64,
+    max_cnt: i64,
+    min2: i64,
+    min_cnt: i64,
+}
+
+impl ChminChmaxSum {
+    pub fn new(x: i64) -> Self {
+        Self {
+            sum: x,
+            len: 1,
+            max: x,
+            min: x,
+            max2: i64::MIN,
+            max_cnt: 1,
+            min2: i64::MAX,
+            min_cnt: 1,
+        }
+    }
+
+    fn fill(&mut self, x: i64) {
+        *self = Self {
+            sum: x * self.len,
+            len: self.len,
+            max_cnt: self.len,
+            min_cnt: self.len,
+            ..Self::new(x)
+        };
+    }
+}
+
+impl Monoid for ChminChmaxSum {
+    fn id() -> Self {
+        Self {
+            sum: 0,
+            len: 0,
+            max: i64::MIN,
+            min: i64::MAX,
+            max2: i64::MIN,
+            max_cnt: 0,
+            min2: i64::MAX,
+            min_cnt: 0,
+        }
+    }
+
+    fn op(&self, other: &Self) -> Self {
+        if self.len == 0 {
+            return *other;
+        }
+        if other.len == 0 {
+            return *self;
+        }
+        let (max, max_cnt, max2) = match self.max.cmp(&other.max) {
+            Ordering::Greater => (self.max, self.max_cnt, self.max2.max(other.max)),
+            Ordering::Less => (other.max, other.max_cnt, self.max.max(other.max2)),
+            Ordering::Equal => (
+                self.max,
+                self.max_cnt + other.max_cnt,
+                self.max2.max(other.max2),
+            ),
+        };
+        let (min, min_cnt, min2) = match self.min.cmp(&other.min) {
+            Ordering::Less => (self.min, self.min_cnt, self.min2.min(other.min)),
+            Ordering::Greater => (other.min, other.min_cnt, self.min.min(other.min2)),
+            Ordering::Equal => (
+                self.min,
+                self.min_cnt + other.min_cnt,
+                self.min2.min(other.min2),
+            ),
+        };
+        Self {
+            sum: self.sum + other.sum,
+            len: self.len + other.len,
+            max,
+            min,
+            max2,
+            max_cnt,
+            min2,
+            min_cnt,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ChminChmaxAdd {
+    add: i64,
+    lo: i64,
+    hi: i64,
+}
+
+impl ChminChmaxAdd {
+    pub fn chmin(x: i64) -> Self {
+        Self {
+            hi: x,
+            ..BeatsMap::<ChminChmaxSum>::id()
+        }
+    }
+
+    pub fn chmax(x: i64) -> Self {
+        Self {
+            lo: x,
+            ..BeatsMap::<ChminChmaxSum>::id()
+        }
+    }
+
+    pub fn add(x: i64) -> Self {
+        Self {
+            add: x,
+            ..BeatsMap::<ChminChmaxSum>::id()
+        }
+    }
+
+    pub fn assign(x: i64) -> Self {
+        Self {
+            add: 0,
+            lo: x,
+            hi: x,
+        }
+    }
+}
+
+fn add_bounded(x: i64, a: i64) -> i64 {
+    if x == i64::MIN || x == i64::MAX {
+        x
+    } else {
+        x + a
+    }
+}
+
+impl BeatsMap<ChminChmaxSum> for ChminChmaxAdd {
+    fn id() -> Self {
+        Self {
+            add: 0,
+            lo: i64::MIN,
+            hi: i64::MAX,
+        }
+    }
+
+    fn comp(&self, other: &Self) -> Self {
+        Self {
+            add: self.add + other.add,
+            lo: add_bounded(other.lo, self.add).clamp(self.lo, self.hi),
+            hi: add_bounded(other.hi, self.add).clamp(self.lo, self.hi),
+        }
+    }
+
+    fn map(&self, x: &ChminChmaxSum) -> Option<ChminChmaxSum> {
+        let mut y = *x;
+        if y.len == 0 {
+            return Some(y);
+        }
+        if self.lo == self.hi {
+            y.fill(self.lo);
+            return Some(y);
+        }
+        if self.add != 0 {
+            y.sum += self.add * y.len;
+            y.max += self.add;
+            y.min += self.add;
+            y.max2 = add_bounded(y.max2, self.add);
+            y.min2 = add_bounded(y.min2, self.add);
+        }
+        if self.lo > y.min {
+            if self.lo >= y.max {
+                y.fill(self.lo);
+            } else if self.lo < y.min2 {
+                y.sum += (self.lo - y.min) * y.min_cnt;
+                if y.max2 == y.min {
+                    y.max2 = self.lo;
+                }
+                y.min = self.lo;
+            } else {
+                return None;
+            }
+        }
+        if self.hi < y.max {
+            if self.hi <= y.min {
+                y.fill(self.hi);
+            } else if self.hi > y.max2 {
+                y.sum -= (y.max - self.hi) * y.max_cnt;
+                if y.min2 == y.max {
+                    y.min2 = self.hi;
+                }
+                y.max = self.hi;
+            } else {
+                return None;
+            }
+        }
+        Some(y)
+    }
+
+    fn is_break(&self, x: &ChminChmaxSum) -> bool {
+        x.len == 0 || self.add == 0 && self.lo <= x.min && x.max <= self.hi
+    }
+}
@@ -0,0 +1,111 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::segtree::Monoid;
+
+pub trait Idempotent: Monoid {}
+
+pub struct SparseTable<T> {
+    t: Vec<Vec<T>>,
+}
+
+impl<T: Idempotent> SparseTable<T> {
+    pub fn len(&self) -> usize {
+        self.t[0].len()
+    }
+
+    pub fn prod(&self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = range_to_pair(range, self.len());
+        if l == r {
+            return T::id();
+        }
+        let k = (r - l).ilog2() as usize;
+        self.t[k][l].op(&self.t[k][r - (1 << k)])
+    }
+}
+
+impl<T: Idempotent> From<Vec<T>> for SparseTable<T> {
+    fn from(a: Vec<T>) -> Self {
+        let n = a.len();
+        let mut t = vec![a];
+        let mut k = 1;
+        while 1 << k <= n {
+            let prev = &t[k - 1];
+            let row = (0..=n - (1 << k))
+                .map(|i| prev[i].op(&prev[i + (1 << (k - 1))]))
+                .collect();
+            t.push(row);
+            k += 1;
+        }
+        Self { t }
+    }
+}
+
+pub struct DisjointSparseTable<T> {
+    t: Vec<Vec<T>>,
+}
+
+impl<T: Monoid> DisjointSparseTable<T> {
+    pub fn len(&self) -> usize {
+        self.t[0].len()
+    }
+
+    pub fn prod(&self, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = range_to_pair(range, self.len());
+        if l == r {
+            return T::id();
+        }
+        let r = r - 1;
+        if l == r {
+            return self.t[0][l].op(&T::id());
+        }
+        let k = (l ^ r).ilog2() as usize;
+        self.t[k][l].op(&self.t[k][r])
+    }
+}
+
+impl<T: Monoid> From<Vec<T>> for DisjointSparseTable<T> {
+    fn from(a: Vec<T>) -> Self {
+        let n = a.len();
+        let mut t = vec![];
+        let mut k = 1;
+        while 1 << k < n {
+            let mut row: Vec<T> = (0..n).map(|_| T::id()).collect();
+            for s in (0..n).step_by(2 << k) {
+                let m = s + (1 << k);
+                if m >= n {
+                    break;
+                }
+                row[m - 1] = a[m - 1].op(&T::id());
+                for i in (s..m - 1).rev() {
+                    row[i] = a[i].op(&row[i + 1]);
+                }
+                row[m] = a[m].op(&T::id());
+                for i in m + 1..(m + (1 << k)).min(n) {
+                    row[i] = row[i - 1].op(&a[i]);
+                }
+            }
+            t.push(row);
+            k += 1;
+        }
+        t.insert(0, a);
+        Self { t }
+    }
+}
+
+fn range_to_pair(range: impl RangeBounds<usize>, n: usize) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Bound::Included(&l) => l,
+        Bound::Excluded(&l) => l + 1,
+        Bound::Unbounded => 0,
+    };
+    let r = match range.end_bound() {
+        Bound::Included(&r) => r + 1,
+        Bound::Excluded(&r) => r,
+        Bound::Unbounded => n,
+    };
+    assert!(
+        l <= r && r <= n,
+        "out of range (len = {n}, l = {l}, r = {r})"
+    );
+    (l, r)
+}
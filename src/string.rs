@@ -0,0 +1,186 @@
+use std::{
+    cmp::Reverse,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{montgomery::Montgomery, suffix_array::SuffixArrayIndex};
+
+pub fn z_array<T: Eq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+    let (mut l, mut r) = (0, 0);
+    for i in 1..n {
+        let mut k = if i < r { z[i - l].min(r - i) } else { 0 };
+        while i + k < n && s[k] == s[i + k] {
+            k += 1;
+        }
+        z[i] = k;
+        if i + k > r {
+            (l, r) = (i, i + k);
+        }
+    }
+    z
+}
+
+pub fn prefix_function<T: Eq>(s: &[T]) -> Vec<usize> {
+    let mut pi = vec![0; s.len()];
+    for i in 1..s.len() {
+        let mut k = pi[i - 1];
+        while k > 0 && s[i] != s[k] {
+            k = pi[k - 1];
+        }
+        if s[i] == s[k] {
+            k += 1;
+        }
+        pi[i] = k;
+    }
+    pi
+}
+
+pub fn kmp_automaton(s: &[usize], sigma: usize) -> Vec<Vec<usize>> {
+    let pi = prefix_function(s);
+    let mut next: Vec<Vec<usize>> = Vec::with_capacity(s.len() + 1);
+    for i in 0..=s.len() {
+        let row = (0..sigma)
+            .map(|c| {
+                if i < s.len() && s[i] == c {
+                    i + 1
+                } else if i == 0 {
+                    0
+                } else {
+                    next[pi[i - 1]][c]
+                }
+            })
+            .collect();
+        next.push(row);
+    }
+    next
+}
+
+pub fn kmp_search<T: Eq>(pattern: &[T], text: &[T]) -> Vec<usize> {
+    if pattern.is_empty() {
+        return (0..=text.len()).collect();
+    }
+    let pi = prefix_function(pattern);
+    let mut res = vec![];
+    let mut k = 0;
+    for (i, c) in text.iter().enumerate() {
+        while k > 0 && (k == pattern.len() || *c != pattern[k]) {
+            k = pi[k - 1];
+        }
+        if *c == pattern[k] {
+            k += 1;
+        }
+        if k == pattern.len() {
+            res.push(i + 1 - k);
+        }
+    }
+    res
+}
+
+pub fn manacher<T: Eq>(s: &[T]) -> Vec<usize> {
+    let t: Vec<Option<&T>> = (0..2 * s.len() + 1)
+        .map(|i| if i % 2 == 1 { Some(&s[i / 2]) } else { None })
+        .collect();
+    let mut rad = vec![0; t.len()];
+    let (mut c, mut r) = (0, 0);
+    for i in 0..t.len() {
+        let mut k = if i < r { rad[2 * c - i].min(r - i) } else { 1 };
+        while k <= i && i + k < t.len() && t[i - k] == t[i + k] {
+            k += 1;
+        }
+        rad[i] = k;
+        if i + k > r {
+            (c, r) = (i, i + k);
+        }
+    }
+    if s.is_empty() {
+        return vec![];
+    }
+    rad[1..t.len() - 1].iter().map(|&k| k - 1).collect()
+}
+
+pub fn runs<T: Ord>(s: &[T]) -> Vec<(usize, usize, usize)> {
+    let n = s.len();
+    let fwd = SuffixArrayIndex::new(s.iter().collect::<Vec<_>>());
+    let rev = SuffixArrayIndex::new(s.iter().rev().collect::<Vec<_>>());
+    let inv = SuffixArrayIndex::new(s.iter().map(Reverse).collect::<Vec<_>>());
+    let mut res = vec![];
+    for rank in [fwd.rank(), inv.rank()] {
+        let mut stk: Vec<usize> = vec![];
+        for i in (0..n).rev() {
+            while stk.last().is_some_and(|&j| rank[j] > rank[i]) {
+                stk.pop();
+            }
+            let j = stk.last().copied().unwrap_or(n);
+            stk.push(i);
+            let p = j - i;
+            let l = i - rev.lcp(n - i, n - j);
+            let r = j + fwd.lcp(i, j);
+            if r - l >= 2 * p {
+                res.push((l, r, p));
+            }
+        }
+    }
+    res.sort_unstable();
+    res.dedup_by_key(|&mut (l, r, _)| (l, r));
+    res
+}
+
+pub const HASH_MOD: u64 = (1 << 61) - 1;
+
+#[derive(Clone, Debug)]
+pub struct RollingHash {
+    mont: Montgomery<u64>,
+    pow: Vec<u64>,
+    hash: Vec<u64>,
+}
+
+impl RollingHash {
+    pub fn new<T: Copy + Into<u64>>(s: &[T], base: u64) -> Self {
+        assert!(base < HASH_MOD);
+        let mont = Montgomery::<u64>::new(HASH_MOD);
+        let mut pow = Vec::with_capacity(s.len() + 1);
+        let mut hash = Vec::with_capacity(s.len() + 1);
+        pow.push(1);
+        hash.push(0);
+        for &c in s {
+            let x = mont.mul(*hash.last().unwrap(), base) + c.into() % HASH_MOD;
+            hash.push(x % HASH_MOD);
+            pow.push(mont.mul(*pow.last().unwrap(), base));
+        }
+        Self { mont, pow, hash }
+    }
+
+    pub fn len(&self) -> usize {
+        self.hash.len() - 1
+    }
+
+    pub fn hash(&self, range: impl RangeBounds<usize>) -> u64 {
+        let l = match range.start_bound() {
+            Bound::Included(&l) => l,
+            Bound::Excluded(&l) => l + 1,
+            Bound::Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Bound::Included(&r) => r + 1,
+            Bound::Excluded(&r) => r,
+            Bound::Unbounded => self.len(),
+        };
+        assert!(
+            l <= r && r <= self.len(),
+            "out of range (len = {}, l = {l}, r = {r})",
+            self.len()
+        );
+        let x = self.hash[r] + HASH_MOD - self.mont.mul(self.hash[l], self.pow[r - l]);
+        x % HASH_MOD
+    }
+
+    pub fn concat(&self, h1: u64, h2: u64, len2: usize) -> u64 {
+        (self.mont.mul(h1, self.pow[len2]) + h2) % HASH_MOD
+    }
+}
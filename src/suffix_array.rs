@@ -1,3 +1,7 @@
+use std::ops::Range;
+
+use crate::{monoids::Min, sparse_table::SparseTable};
+
 pub fn suffix_array(s: &[u8]) -> Vec<usize> {
     if s.len() <= 20 {
         suffix_array_naive(s)
@@ -6,6 +10,39 @@ pub fn suffix_array(s: &[u8]) -> Vec<usize> {
     }
 }
 
+pub fn suffix_array_usize(s: &[usize], max: usize) -> Vec<usize> {
+    assert!(s.iter().all(|&c| c <= max));
+    if s.len() <= 20 {
+        suffix_array_naive(s)
+    } else {
+        sa_is(s, max)
+    }
+}
+
+pub fn suffix_array_u32(s: &[u32], max: u32) -> Vec<usize> {
+    let s: Vec<usize> = s.iter().map(|&c| c as usize).collect();
+    suffix_array_usize(&s, max as usize)
+}
+
+pub fn suffix_array_ord<T: Ord>(s: &[T]) -> Vec<usize> {
+    let (s, max) = compress(s);
+    suffix_array_usize(&s, max)
+}
+
+fn compress<T: Ord>(s: &[T]) -> (Vec<usize>, usize) {
+    let mut order: Vec<usize> = (0..s.len()).collect();
+    order.sort_by(|&i, &j| s[i].cmp(&s[j]));
+    let mut t = vec![0; s.len()];
+    let mut max = 0;
+    for k in 1..order.len() {
+        if s[order[k - 1]] < s[order[k]] {
+            max += 1;
+        }
+        t[order[k]] = max;
+    }
+    (t, max)
+}
+
 pub(crate) fn suffix_array_naive<T: Ord>(s: &[T]) -> Vec<usize> {
     let mut sa: Vec<usize> = (0..s.len()).collect();
     sa.sort_by(|&i, &j| s[i..].cmp(&s[j..]));
@@ -130,7 +167,7 @@ pub fn lcp_array<T: Ord>(s: &[T], sa: &[usize]) -> Vec<usize> {
     for (i, &sa) in sa.iter().enumerate() {
         rank[sa] = i;
     }
-    let mut lcp = vec![0; s.len() - 1];
+    let mut lcp = vec![0; s.len().saturating_sub(1)];
     let mut h = 0usize;
     for (i, &rank_i) in rank.iter().enumerate() {
         h = h.saturating_sub(1);
@@ -145,3 +182,114 @@ pub fn lcp_array<T: Ord>(s: &[T], sa: &[usize]) -> Vec<usize> {
     }
     lcp
 }
+
+pub struct SuffixArrayIndex<T> {
+    s: Vec<T>,
+    sa: Vec<usize>,
+    rank: Vec<usize>,
+    lcp: Vec<usize>,
+    st: SparseTable<Min<usize>>,
+}
+
+impl<T: Ord> SuffixArrayIndex<T> {
+    pub fn new(s: Vec<T>) -> Self {
+        let sa = suffix_array_ord(&s);
+        let mut rank = vec![0; s.len()];
+        for (i, &j) in sa.iter().enumerate() {
+            rank[j] = i;
+        }
+        let lcp = lcp_array(&s, &sa);
+        let st = lcp.iter().map(|&h| Min(h)).collect::<Vec<_>>().into();
+        Self {
+            s,
+            sa,
+            rank,
+            lcp,
+            st,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.s.len()
+    }
+
+    pub fn text(&self) -> &[T] {
+        &self.s
+    }
+
+    pub fn sa(&self) -> &[usize] {
+        &self.sa
+    }
+
+    pub fn rank(&self) -> &[usize] {
+        &self.rank
+    }
+
+    pub fn lcp_array(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    pub fn lcp(&self, i: usize, j: usize) -> usize {
+        assert!(i <= self.len() && j <= self.len());
+        if i == j {
+            return self.len() - i;
+        }
+        if i == self.len() || j == self.len() {
+            return 0;
+        }
+        let (a, b) = (
+            self.rank[i].min(self.rank[j]),
+            self.rank[i].max(self.rank[j]),
+        );
+        self.st.prod(a..b).0
+    }
+
+    pub fn sa_range(&self, pattern: &[T]) -> Range<usize> {
+        let prefix = |i: usize| &self.s[i..(i + pattern.len()).min(self.len())];
+        let l = self.sa.partition_point(|&i| prefix(i) < pattern);
+        let r = l + self.sa[l..].partition_point(|&i| prefix(i) <= pattern);
+        l..r
+    }
+
+    pub fn count_occurrences(&self, pattern: &[T]) -> usize {
+        self.sa_range(pattern).len()
+    }
+
+    pub fn find_all(&self, pattern: &[T]) -> Vec<usize> {
+        let mut res = self.sa[self.sa_range(pattern)].to_vec();
+        res.sort_unstable();
+        res
+    }
+
+    pub fn distinct_substrings(&self) -> usize {
+        let n = self.len();
+        n * (n + 1) / 2 - self.lcp.iter().sum::<usize>()
+    }
+
+    pub fn longest_repeated_substring(&self) -> Range<usize> {
+        match self.lcp.iter().enumerate().max_by_key(|&(i, &h)| (h, !i)) {
+            Some((i, &h)) => self.sa[i]..self.sa[i] + h,
+            None => 0..0,
+        }
+    }
+}
+
+pub fn longest_common_substring<T: Ord>(s: &[T], t: &[T]) -> (Range<usize>, Range<usize>) {
+    let all: Vec<&T> = s.iter().chain(t).collect();
+    let (all, max) = compress(&all);
+    let mut u = Vec::with_capacity(s.len() + t.len() + 1);
+    u.extend(all[..s.len()].iter().map(|&c| c + 1));
+    u.push(0);
+    u.extend(all[s.len()..].iter().map(|&c| c + 1));
+    let sa = suffix_array_usize(&u, max + 1);
+    let lcp = lcp_array(&u, &sa);
+    let mut best = (0, 0, 0);
+    for (k, &h) in lcp.iter().enumerate() {
+        let (i, j) = (sa[k].min(sa[k + 1]), sa[k].max(sa[k + 1]));
+        if i < s.len() && j > s.len() && h > best.0 {
+            best = (h, i, j - s.len() - 1);
+        }
+    }
+    let (h, i, j) = best;
+    (i..i + h, j..j + h)
+}
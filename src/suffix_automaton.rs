@@ -0,0 +1,126 @@
+use crate::trie::{Links, Node};
+
+#[derive(Clone)]
+pub struct SuffixAutomaton {
+    nodes: Vec<Node>,
+    link: Vec<usize>,
+    len: Vec<usize>,
+    is_clone: Vec<bool>,
+    last: usize,
+}
+
+impl SuffixAutomaton {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![Node::new()],
+            link: vec![!0],
+            len: vec![0],
+            is_clone: vec![false],
+            last: 0,
+        }
+    }
+
+    pub fn count_node(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn last(&self) -> usize {
+        self.last
+    }
+
+    pub fn transition(&self, i: usize, c: u8) -> Option<usize> {
+        self.nodes[i].transition(c)
+    }
+
+    pub fn links(&self, i: usize) -> Links<'_> {
+        self.nodes[i].links()
+    }
+
+    pub fn link(&self, i: usize) -> Option<usize> {
+        (i != 0).then(|| self.link[i])
+    }
+
+    pub fn len(&self, i: usize) -> usize {
+        self.len[i]
+    }
+
+    fn add_node(&mut self, node: Node, link: usize, len: usize, is_clone: bool) -> usize {
+        self.nodes.push(node);
+        self.link.push(link);
+        self.len.push(len);
+        self.is_clone.push(is_clone);
+        self.nodes.len() - 1
+    }
+
+    pub fn push(&mut self, c: u8) -> usize {
+        let cur = self.add_node(Node::new(), 0, self.len[self.last] + 1, false);
+        let mut p = self.last;
+        self.last = cur;
+        while p != !0 && self.nodes[p].transition(c).is_none() {
+            self.nodes[p].set_transition(c, cur);
+            p = self.link[p];
+        }
+        if p == !0 {
+            return cur;
+        }
+        let q = self.nodes[p].transition(c).unwrap();
+        if self.len[p] + 1 == self.len[q] {
+            self.link[cur] = q;
+            return cur;
+        }
+        let clone = self.add_node(self.nodes[q].clone(), self.link[q], self.len[p] + 1, true);
+        while p != !0 && self.nodes[p].transition(c) == Some(q) {
+            self.nodes[p].set_transition(c, clone);
+            p = self.link[p];
+        }
+        self.link[q] = clone;
+        self.link[cur] = clone;
+        cur
+    }
+
+    pub fn contains(&self, s: &[u8]) -> bool {
+        let mut i = 0;
+        for &c in s {
+            let Some(j) = self.transition(i, c) else {
+                return false;
+            };
+            i = j;
+        }
+        true
+    }
+
+    pub fn distinct_substrings(&self) -> usize {
+        (1..self.nodes.len())
+            .map(|i| self.len[i] - self.len[self.link[i]])
+            .sum()
+    }
+
+    pub fn endpos_counts(&self) -> Vec<usize> {
+        let n = self.nodes.len();
+        let mut cnt: Vec<usize> = (0..n)
+            .map(|i| (i != 0 && !self.is_clone[i]) as usize)
+            .collect();
+        let mut order: Vec<usize> = (1..n).collect();
+        order.sort_unstable_by_key(|&i| !self.len[i]);
+        for i in order {
+            cnt[self.link[i]] += cnt[i];
+        }
+        cnt
+    }
+}
+
+impl Default for SuffixAutomaton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&[u8]> for SuffixAutomaton {
+    fn from(s: &[u8]) -> Self {
+        let mut sa = Self::new();
+        for &c in s {
+            sa.push(c);
+        }
+        sa
+    }
+}
@@ -1,20 +1,34 @@
 mod aho_corasick;
+mod binary_trie;
 pub mod bipartite_matching;
 mod bitset;
 mod cartesian_tree;
 mod cht;
 mod dsu;
+mod dynamic_segtree;
+mod eertree;
 mod factorize;
+mod fenwick;
 mod floor_sum;
+mod general_matching;
 mod integer;
 mod lazy_segtree;
+mod li_chao;
 mod matrix;
 mod max_flow;
 mod modint;
+mod monoids;
 mod montgomery;
 mod poly;
 mod segtree;
+mod segtree_2d;
+mod segtree_beats;
 mod simple_rng;
+mod sparse_table;
+mod string;
 mod suffix_array;
+mod suffix_automaton;
+mod treap;
 mod trie;
 mod two_sat;
+mod wavelet_matrix;
@@ -1,4 +1,4 @@
-use crate::{aho_corasick::*, trie::*};
+use crate::{aho_corasick::*, simple_rng::*, trie::*};
 
 #[test]
 fn test() {
@@ -11,4 +11,114 @@ fn test() {
     assert_eq!(ac.transition(0, b'a'), Some(1));
     assert_eq!(ac.transition(1, b'b'), Some(2));
     assert_eq!(ac.suffix(2), Some(6));
+    assert_eq!(ac.pattern_count(), 3);
+    assert_eq!(ac.count_matches(b"xabcdast"), [1, 1, 1]);
+    assert_eq!(
+        ac.find_iter(b"abcd").collect::<Vec<_>>(),
+        [
+            (0, ac.patterns_at(3)[0]),
+            (1, ac.patterns_at(ac.pattern_node(2))[0])
+        ]
+    );
+}
+
+#[test]
+fn from_trie_duplicates() {
+    let mut trie = Trie::new();
+    trie.insert_slice(b"ab");
+    trie.insert_slice(b"b");
+    trie.insert_slice(b"ab");
+    let ac = AhoCorasick::from_trie(trie);
+    assert_eq!(ac.pattern_count(), 3);
+    assert_eq!(ac.pattern_node(0), ac.pattern_node(1));
+    assert_eq!(ac.count_matches(b"abab"), [2, 2, 2]);
+    let mut matches: Vec<_> = ac.find_iter(b"ab").collect();
+    matches.sort();
+    assert_eq!(matches, [(0, 0), (0, 1), (1, 2)]);
+
+    let mut trie = Trie::new();
+    trie.insert_slice(b"ab");
+    trie.insert_slice(b"bc");
+    trie.remove(b"bc".iter().copied());
+    let ac = AhoCorasick::from_trie(trie);
+    assert_eq!(ac.pattern_count(), 1);
+    assert_eq!(ac.find_iter(b"abc").collect::<Vec<_>>(), [(0, 0)]);
+    assert_eq!(ac.next(2, b'c'), 0);
+}
+
+#[test]
+fn empty_pattern() {
+    let ac = AhoCorasick::new(["a", "a", "aa", ""]);
+    assert_eq!(ac.count_matches(b"aa"), [2, 2, 1, 3]);
+    assert_eq!(ac.count_matches(b""), [0, 0, 0, 1]);
+    let mut matches: Vec<_> = ac.find_iter(b"aa").collect();
+    matches.sort();
+    assert_eq!(
+        matches,
+        [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 0),
+            (1, 1),
+            (1, 3),
+            (2, 3)
+        ]
+    );
+}
+
+#[test]
+fn aho_corasick_matches_random() {
+    let mut rng = Rng::new(2045);
+    for _ in 0..50 {
+        let patterns: Vec<Vec<u8>> = (0..rng.range(0..8))
+            .map(|_| {
+                (0..rng.range(0..5))
+                    .map(|_| b'a' + rng.range(0..3) as u8)
+                    .collect()
+            })
+            .collect();
+        let text: Vec<u8> = (0..rng.range(0..40))
+            .map(|_| b'a' + rng.range(0..3) as u8)
+            .collect();
+        let ac = AhoCorasick::new(&patterns);
+        assert_eq!(ac.pattern_count(), patterns.len());
+
+        let mut naive = vec![];
+        for end in 0..=text.len() {
+            for (id, p) in patterns.iter().enumerate() {
+                if text[..end].ends_with(p) {
+                    naive.push((end - p.len(), id));
+                }
+            }
+        }
+        let mut matches: Vec<(usize, usize)> = ac.find_iter(&text).collect();
+        let key = |&(start, id): &(usize, usize)| (start + patterns[id].len(), start, id);
+        naive.sort_by_key(key);
+        matches.sort_by_key(key);
+        assert_eq!(matches, naive);
+
+        let counts = ac.count_matches(&text);
+        for (id, p) in patterns.iter().enumerate() {
+            let naive = (0..=text.len())
+                .filter(|&i| text[i..].starts_with(p))
+                .count();
+            assert_eq!(counts[id], naive);
+            assert!(ac.patterns_at(ac.pattern_node(id)).contains(&id));
+            assert_eq!(ac.depth(ac.pattern_node(id)), p.len());
+        }
+
+        let alphabet = b"abc";
+        let table = ac.goto_table(alphabet);
+        assert_eq!(table.len(), ac.count_node());
+        for (v, row) in table.iter().enumerate() {
+            for (&w, &c) in row.iter().zip(alphabet) {
+                assert_eq!(w, ac.next(v, c));
+            }
+            if let Some(w) = ac.output(v) {
+                assert!(!ac.patterns_at(w).is_empty() && ac.depth(w) < ac.depth(v));
+            }
+        }
+    }
 }
@@ -0,0 +1,42 @@
+use crate::{binary_trie::*, simple_rng::*};
+
+#[test]
+fn binary_trie_random() {
+    let mut rng = Rng::new(2039);
+    for bits in [0, 1, 5, 64] {
+        let mut trie = BinaryTrie::new(bits);
+        let mut naive: Vec<u64> = vec![];
+        let key = |rng: &mut Rng| {
+            if bits == 64 {
+                rng.next_u64() >> rng.range(0..64)
+            } else {
+                rng.range(0..1 << bits)
+            }
+        };
+        for _ in 0..1000 {
+            let x = key(&mut rng);
+            if rng.range(0..3) == 0 {
+                let pos = naive.iter().position(|&y| y == x);
+                assert_eq!(trie.remove_one(x), pos.is_some());
+                if let Some(pos) = pos {
+                    naive.remove(pos);
+                }
+            } else {
+                trie.insert(x);
+                naive.push(x);
+            }
+            naive.sort();
+
+            let x = key(&mut rng);
+            assert_eq!(trie.len(), naive.len());
+            assert_eq!(trie.count(x), naive.iter().filter(|&&y| y == x).count());
+            assert_eq!(trie.count_lt(x), naive.partition_point(|&y| y < x));
+            let k = rng.range(..=naive.len());
+            assert_eq!(trie.kth(k), naive.get(k).copied());
+            assert_eq!(trie.prev(x), naive.iter().rev().find(|&&y| y < x).copied());
+            assert_eq!(trie.next(x), naive.iter().find(|&&y| y > x).copied());
+            assert_eq!(trie.xor_min(x), naive.iter().map(|&y| x ^ y).min());
+            assert_eq!(trie.xor_max(x), naive.iter().map(|&y| x ^ y).max());
+        }
+    }
+}
@@ -56,3 +56,114 @@ fn bipartite_matching_random() {
         }
     }
 }
+
+#[test]
+fn bipartite_matching_small_random() {
+    let mut rng = Rng::new(2026);
+    for _ in 0..300 {
+        let n = rng.range(1..=6);
+        let m = rng.range(1..=6);
+        let mut bm = BipartiteMatching::new(n, m);
+        let mut adj = vec![0u32; m];
+        for _ in 0..rng.range(0..=n * m) {
+            let (u, v) = (rng.range(..n), rng.range(..m));
+            bm.edge(u, v);
+            adj[v] |= 1 << u;
+        }
+        let mut best = vec![0; 1 << n];
+        for v in 0..m {
+            for used in (0..1usize << n).rev() {
+                for u in 0..n {
+                    if used >> u & 1 == 1 && adj[v] >> u & 1 == 1 {
+                        best[used] = best[used].max(best[used ^ 1 << u] + 1);
+                    }
+                }
+            }
+        }
+        let size = bm.run().count();
+        assert_eq!(size, best[(1 << n) - 1], "adj={adj:?}");
+    }
+}
+
+#[test]
+fn bipartite_matching_konig() {
+    let mut rng = Rng::new(2026);
+
+    for _ in 0..300 {
+        let n = rng.range(1..=5);
+        let m = rng.range(1..=5);
+        let mut edges = vec![];
+        for _ in 0..rng.range(0..=9) {
+            let e = (rng.range(..n), rng.range(..m));
+            if !edges.contains(&e) {
+                edges.push(e);
+            }
+        }
+
+        let mut bm = BipartiteMatching::new(n, m);
+        for &(u, v) in &edges {
+            bm.edge(u, v);
+        }
+        let k = bm.run().count();
+
+        let mut best = 0;
+        let mut can = vec![false; edges.len()];
+        let mut must = vec![true; edges.len()];
+        for s in 0usize..1 << edges.len() {
+            let mut used_l = 0u32;
+            let mut used_r = 0u32;
+            let mut ok = true;
+            for (i, &(u, v)) in edges.iter().enumerate() {
+                if s >> i & 1 == 1 {
+                    ok &= used_l >> u & 1 == 0 && used_r >> v & 1 == 0;
+                    used_l |= 1 << u;
+                    used_r |= 1 << v;
+                }
+            }
+            if !ok {
+                continue;
+            }
+            let size = s.count_ones() as usize;
+            if size > best {
+                best = size;
+                can.fill(false);
+                must.fill(true);
+            }
+            if size == best {
+                for i in 0..edges.len() {
+                    can[i] |= s >> i & 1 == 1;
+                    must[i] &= s >> i & 1 == 1;
+                }
+            }
+        }
+        assert_eq!(k, best, "n={n}, m={m}, edges={edges:?}");
+
+        let (cl, cr) = bm.min_vertex_cover();
+        assert_eq!(cl.len() + cr.len(), k);
+        for &(u, v) in &edges {
+            assert!(cl.contains(&u) || cr.contains(&v), "edges={edges:?}");
+        }
+
+        let (il, ir) = bm.max_independent_set();
+        assert_eq!(il.len() + ir.len(), n + m - k);
+        for &(u, v) in &edges {
+            assert!(!(il.contains(&u) && ir.contains(&v)), "edges={edges:?}");
+        }
+
+        let dm = bm.dulmage_mendelsohn();
+        for (i, &(u, v)) in edges.iter().enumerate() {
+            assert_eq!(
+                dm.can_match(u, v),
+                can[i],
+                "edges={edges:?}, e={:?}",
+                (u, v)
+            );
+            assert_eq!(
+                dm.must_match(u, v),
+                must[i],
+                "edges={edges:?}, e={:?}",
+                (u, v)
+            );
+        }
+    }
+}
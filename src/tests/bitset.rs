@@ -1,4 +1,4 @@
-use crate::bitset::*;
+use crate::{bitset::*, simple_rng::*};
 
 #[test]
 fn test() {
@@ -14,3 +14,134 @@ fn test() {
     assert_eq!(a.flip_bit(2), false);
     assert_eq!(a.display_bits().to_string(), "0110100011101000");
 }
+
+fn check(v: &BitVec, naive: &[bool]) {
+    assert_eq!(v.len(), naive.len());
+    let s: String = naive.iter().map(|&f| if f { '1' } else { '0' }).collect();
+    assert_eq!(v.to_string(), s);
+    assert_eq!(s.parse::<BitVec>(), Ok(v.clone()));
+    assert_eq!(v.count_ones(), naive.iter().filter(|&&f| f).count());
+    let pos: Vec<usize> = (0..naive.len()).filter(|&i| naive[i]).collect();
+    assert_eq!(v.one_positions().collect::<Vec<_>>(), pos);
+    assert_eq!(v.find_first(), pos.first().copied());
+    for i in 0..naive.len() {
+        assert_eq!(v.find_next(i), pos.iter().copied().find(|&j| j > i));
+    }
+}
+
+#[test]
+fn bitvec_random() {
+    let mut rng = Rng::new(2047);
+    for _ in 0..100 {
+        let n = rng.range(0..200);
+        let mut naive: Vec<bool> = (0..n).map(|_| rng.range(0..2) == 1).collect();
+        let mut v: BitVec = naive.iter().copied().collect();
+        check(&v, &naive);
+        for _ in 0..20 {
+            match rng.range(0..7) {
+                0 => {
+                    let k = rng.range(0..250);
+                    v <<= k;
+                    naive = (0..n).map(|i| i >= k && naive[i - k]).collect();
+                }
+                1 => {
+                    let k = rng.range(0..250);
+                    v = &v >> k;
+                    naive = (0..n).map(|i| i + k < n && naive[i + k]).collect();
+                }
+                2 => {
+                    let l = rng.range(0..n + 1);
+                    let r = rng.range(l..n + 1);
+                    let f = rng.range(0..2) == 1;
+                    v.set_range(l..r, f);
+                    naive[l..r].fill(f);
+                }
+                3 => {
+                    v = !v;
+                    naive.iter_mut().for_each(|f| *f = !*f);
+                }
+                4 => {
+                    v.reverse_bits();
+                    naive.reverse();
+                }
+                _ => {
+                    let other: Vec<bool> = (0..n).map(|_| rng.range(0..2) == 1).collect();
+                    let w: BitVec = other.iter().copied().collect();
+                    let op = rng.range(0..3);
+                    v = match op {
+                        0 => v & &w,
+                        1 => v | &w,
+                        _ => v ^ &w,
+                    };
+                    for (f, g) in naive.iter_mut().zip(other) {
+                        *f = match op {
+                            0 => *f & g,
+                            1 => *f | g,
+                            _ => *f ^ g,
+                        };
+                    }
+                }
+            }
+            check(&v, &naive);
+        }
+    }
+}
+
+#[test]
+fn bits_subset_sum() {
+    let mut rng = Rng::new(20471);
+    for _ in 0..50 {
+        let a: Vec<usize> = (0..rng.range(0..10)).map(|_| rng.range(1..30)).collect();
+        let mut dp = Bits::<2>::ZERO;
+        dp.set_bit(0, true);
+        for &x in &a {
+            dp |= &(dp << x);
+        }
+        let mut naive = [false; 128];
+        naive[0] = true;
+        for &x in &a {
+            for s in (x..128).rev() {
+                naive[s] |= naive[s - x];
+            }
+        }
+        for (s, &f) in naive.iter().enumerate() {
+            assert_eq!(dp.bit(s), f);
+        }
+        assert_eq!(dp.to_string().parse::<Bits<2>>(), Ok(dp));
+        let mut rev = dp;
+        rev.reverse_bits();
+        rev >>= 3;
+        rev.set_range(..5, true);
+        assert_eq!(rev.find_first(), Some(0));
+        let expected: Vec<bool> = (0..128)
+            .map(|i| i < 5 || i < 125 && naive[124 - i])
+            .collect();
+        for (i, &f) in expected.iter().enumerate() {
+            assert_eq!(rev.bit(i), f);
+        }
+    }
+    assert_eq!("2".parse::<BitVec>(), Err(ParseBitsError));
+    assert_eq!("1".repeat(129).parse::<Bits<2>>(), Err(ParseBitsError));
+}
+
+#[test]
+fn rank_select_random() {
+    let mut rng = Rng::new(2040);
+    for n in [0, 1, 63, 64, 65, 200] {
+        let a: Vec<bool> = (0..n).map(|_| rng.range(0..3) == 0).collect();
+        let rs: RankSelect = a.iter().copied().collect();
+        assert_eq!(rs.len(), n);
+        assert_eq!(rs.count_ones(), a.iter().filter(|&&f| f).count());
+        for i in 0..=n {
+            let ones = a[..i].iter().filter(|&&f| f).count();
+            assert_eq!(rs.rank1(i), ones);
+            assert_eq!(rs.rank0(i), i - ones);
+        }
+        let ones: Vec<usize> = (0..n).filter(|&i| a[i]).collect();
+        let zeros: Vec<usize> = (0..n).filter(|&i| !a[i]).collect();
+        for k in 0..=n {
+            assert_eq!(rs.select1(k), ones.get(k).copied());
+            assert_eq!(rs.select0(k), zeros.get(k).copied());
+        }
+    }
+}
@@ -1,5 +1,31 @@
 use crate::{cht::*, simple_rng::*};
 
+fn lower_envelope(lines: &[(usize, Line<i64>)]) -> Vec<(usize, Line<i64>)> {
+    let mut hull: Vec<_> = lines
+        .iter()
+        .copied()
+        .filter(|&(i, s)| {
+            let dup = lines
+                .iter()
+                .any(|&(j, t)| t.a == s.a && (t.b, j) < (s.b, i));
+            // s is the minimum exactly on the open interval (l, r), as fractions
+            let mut l: Option<(i64, i64)> = None;
+            let mut r = l;
+            for &(_, t) in lines {
+                if t.a > s.a && l.is_none_or(|l| (s.b - t.b) * l.1 > l.0 * (t.a - s.a)) {
+                    l = Some((s.b - t.b, t.a - s.a));
+                }
+                if t.a < s.a && r.is_none_or(|r| (t.b - s.b) * r.1 < r.0 * (s.a - t.a)) {
+                    r = Some((t.b - s.b, s.a - t.a));
+                }
+            }
+            !dup && l.zip(r).is_none_or(|(l, r)| l.0 * r.1 < r.0 * l.1)
+        })
+        .collect();
+    hull.sort_by_key(|&(_, l)| -l.a);
+    hull
+}
+
 #[test]
 fn cht_random() {
     let mut rng = Rng::new(1);
@@ -8,18 +34,100 @@ fn cht_random() {
             .map(|_| line(rng.range(-100..=100), rng.range(-10000..=10000)))
             .collect();
         let mut a_min = lines.iter().map(|l| l.a).min().unwrap();
+        let mut a_max = lines.iter().map(|l| l.a).max().unwrap();
         let mut cht = Cht::from(lines.clone());
-        for i in 0..20 {
+        let mut cht_max = ChtMax::from(lines.clone());
+        let mut max_lines = lines.clone();
+        let mut hull = lower_envelope(&lines.iter().copied().enumerate().collect::<Vec<_>>());
+        for _ in 0..40 {
+            match rng.range(0..4) {
+                0 => {
+                    let l = line(rng.range(-100..=a_min), rng.range(-10000..=10000));
+                    a_min = l.a;
+                    lines.push(l);
+                    assert_eq!(cht.push_line(l), lines.len() - 1);
+                    hull.push((lines.len() - 1, l));
+                    hull = lower_envelope(&hull);
+
+                    let l = line(rng.range(a_max..=100), rng.range(-10000..=10000));
+                    a_max = l.a;
+                    max_lines.push(l);
+                    assert_eq!(cht_max.push_line(l), max_lines.len() - 1);
+                }
+                1 => {
+                    let front = (!hull.is_empty()).then(|| hull.remove(0));
+                    assert_eq!(cht.pop_front(), front);
+                }
+                2 => assert_eq!(cht.pop_back(), hull.pop()),
+                _ => {}
+            }
+            assert_eq!(cht.len(), hull.len());
+
             let x = rng.range(-10000..=10000);
-            let ans = cht.y_min(x);
-            let naive = lines.iter().map(|l| l.y(x)).min().unwrap();
-            assert_eq!(ans, naive);
-            if i % 5 == 0 {
-                let l = line(rng.range(-100..=a_min), rng.range(-10000..=10000));
-                a_min = a_min.min(l.a);
-                lines.push(l);
-                cht.push_line(l);
+            if !hull.is_empty() {
+                let naive = hull.iter().map(|(_, l)| l.y(x)).min().unwrap();
+                assert_eq!(cht.y_min(x), naive);
+                assert_eq!(lines[cht.argmin(x)].y(x), naive);
+            }
+            let naive = max_lines.iter().map(|l| l.y(x)).max().unwrap();
+            assert_eq!(cht_max.y_max(x), naive);
+            assert_eq!(max_lines[cht_max.argmax(x)].y(x), naive);
+        }
+    }
+}
+
+#[test]
+fn dynamic_cht_random() {
+    let mut rng = Rng::new(2049);
+    for _ in 0..100 {
+        let mut cht = DynamicCht::new();
+        let mut lines = vec![];
+        for _ in 0..30 {
+            let l = line(rng.range(-20..=20), rng.range(-200..=200));
+            cht.insert(l);
+            lines.push(l);
+            for _ in 0..10 {
+                let x = rng.range(-30..=30);
+                assert_eq!(cht.y_min(x), lines.iter().map(|l| l.y(x)).min().unwrap());
+                assert_eq!(cht.y_max(x), lines.iter().map(|l| l.y(x)).max().unwrap());
             }
         }
     }
 }
+
+#[test]
+fn cht_monotone_argmin_max() {
+    let mut rng = Rng::new(2050);
+    for _ in 0..100 {
+        let mut lines: Vec<_> = (0..20)
+            .map(|_| line(rng.range(-100..=100), rng.range(-10000..=10000)))
+            .collect();
+        let cht = Cht::from(lines.clone());
+        let mut monotone = Cht::from(lines.clone());
+        let cht_max = ChtMax::from(lines.clone());
+        let mut monotone_max: ChtMax<i64> = lines.iter().copied().collect();
+        let mut xs: Vec<i64> = (0..30).map(|_| rng.range(-1000..=1000)).collect();
+        xs.sort();
+        for &x in &xs {
+            let min = lines.iter().map(|l| l.y(x)).min().unwrap();
+            let max = lines.iter().map(|l| l.y(x)).max().unwrap();
+            assert_eq!(lines[cht.argmin(x)].y(x), min);
+            assert_eq!(monotone.y_min_increasing(x), min);
+            assert_eq!(lines[monotone.argmin_increasing(x)].y(x), min);
+            assert_eq!(cht_max.y_max(x), max);
+            assert_eq!(lines[cht_max.argmax(x)].y(x), max);
+            assert_eq!(monotone_max.y_max_increasing(x), max);
+            assert_eq!(lines[monotone_max.argmax_increasing(x)].y(x), max);
+        }
+
+        lines.sort_by_key(|l| -l.a);
+        let mut cht = Cht::new();
+        for (i, &l) in lines.iter().enumerate() {
+            assert_eq!(cht.push_line(l), i);
+        }
+        while let Some((id, l)) = cht.pop_back() {
+            assert_eq!(lines[id], l);
+        }
+        assert_eq!(cht.len(), 0);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::dsu::*;
+use crate::{dsu::*, simple_rng::*};
 
 #[test]
 fn dsu_merge() {
@@ -72,3 +72,164 @@ fn dsu_merge_drop() {
 
     assert_eq!(Rc::strong_count(&rc), 1);
 }
+
+#[test]
+fn dsu_find() {
+    let mut rng = Rng::new(2029);
+    let n = 30;
+    let mut dsu = Dsu::new(n);
+    let mut naive: Vec<usize> = (0..n).collect();
+    for _ in 0..100 {
+        let i = rng.range(..n);
+        let j = rng.range(..n);
+        let res = dsu.unite(i, j);
+        assert_eq!(res.is_united, naive[i] != naive[j]);
+        let (a, b) = (naive[i], naive[j]);
+        naive.iter_mut().filter(|c| **c == b).for_each(|c| *c = a);
+        for k in 0..n {
+            let r = dsu.find(k);
+            assert!(dsu.is_root(r));
+            assert_eq!(dsu.root(k), r);
+            assert_eq!(
+                dsu.size(k),
+                naive.iter().filter(|&&c| c == naive[k]).count()
+            );
+        }
+    }
+}
+
+#[test]
+fn rollback_dsu() {
+    let mut rng = Rng::new(2029);
+    let n = 20;
+    let mut dsu = RollbackDsu::new(n);
+    let mut stack = vec![];
+    for _ in 0..300 {
+        if rng.range(0..3) == 0 && !stack.is_empty() {
+            let (t, snapshot): (usize, Vec<Comp>) = stack.pop().unwrap();
+            dsu.rollback(t);
+            assert_eq!(dsu.time(), t);
+            assert!((0..n).map(|i| dsu.comp(i)).eq(snapshot));
+        } else {
+            stack.push((dsu.time(), (0..n).map(|i| dsu.comp(i)).collect()));
+            dsu.unite(rng.range(..n), rng.range(..n));
+        }
+    }
+    dsu.rollback(0);
+    assert_eq!(dsu.comps().count(), n);
+    assert!(!dsu.undo());
+}
+
+#[test]
+fn persistent_dsu() {
+    let mut rng = Rng::new(2029);
+    let n = 20;
+    let mut dsu = PersistentDsu::new(n);
+    let mut plain = Dsu::new(n);
+    let mut snapshots = vec![plain.clone()];
+    for t in 1..=40 {
+        for _ in 0..rng.range(0..3) {
+            let (i, j) = (rng.range(..n), rng.range(..n));
+            assert_eq!(dsu.unite(i, j, t).is_united, plain.unite(i, j).is_united);
+        }
+        snapshots.push(plain.clone());
+    }
+    for (t, snapshot) in snapshots.iter().enumerate() {
+        for i in 0..n {
+            assert_eq!(dsu.size_at(i, t), snapshot.size(i));
+            for j in 0..n {
+                assert_eq!(
+                    dsu.same_at(i, j, t),
+                    snapshot.root(i) == snapshot.root(j),
+                    "t={t}, i={i}, j={j}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn weighted_dsu() {
+    let mut rng = Rng::new(2030);
+    for _ in 0..50 {
+        let n = 15;
+        let x: Vec<i64> = (0..n).map(|_| rng.range(-100..=100)).collect();
+        let mut dsu = WeightedDsu::<i64>::new(n);
+        let mut plain = Dsu::new(n);
+        for _ in 0..40 {
+            let (i, j) = (rng.range(..n), rng.range(..n));
+            let same = plain.root(i) == plain.root(j);
+            if rng.range(0..4) == 0 {
+                let w = x[j] - x[i] + 1;
+                let res = dsu.unite(i, j, w);
+                if same {
+                    assert_eq!(res, None);
+                    continue;
+                }
+                assert!(res.unwrap().is_united);
+                plain.unite(i, j);
+                let res = dsu.unite(i, j, w - 1);
+                assert_eq!(res, None);
+                let d = x[j] - x[i] + 1;
+                let res = dsu.unite(j, i, -d);
+                assert!(!res.unwrap().is_united);
+                break;
+            }
+            let res = dsu.unite(i, j, x[j] - x[i]).unwrap();
+            assert_eq!(res, plain.unite(i, j));
+            for k in 0..n {
+                for l in 0..n {
+                    let d = (plain.root(k) == plain.root(l)).then(|| x[l] - x[k]);
+                    assert_eq!(dsu.diff(k, l), d);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Perm([u8; 3]);
+
+impl Group for Perm {
+    fn id() -> Self {
+        Perm([0, 1, 2])
+    }
+    fn op(&self, other: &Self) -> Self {
+        Perm(other.0.map(|i| self.0[i as usize]))
+    }
+    fn inv(&self) -> Self {
+        let mut inv = [0; 3];
+        for (i, &p) in self.0.iter().enumerate() {
+            inv[p as usize] = i as u8;
+        }
+        Perm(inv)
+    }
+}
+
+#[test]
+fn weighted_dsu_non_commutative() {
+    let mut rng = Rng::new(2030);
+    let perms = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    let n = 12;
+    let x: Vec<Perm> = (0..n).map(|_| Perm(perms[rng.range(..6)])).collect();
+    let mut dsu = WeightedDsu::<Perm>::new(n);
+    for _ in 0..30 {
+        let (i, j) = (rng.range(..n), rng.range(..n));
+        let w = x[i].inv().op(&x[j]);
+        assert!(dsu.unite(i, j, w).is_some());
+    }
+    for i in 0..n {
+        for j in 0..n {
+            if let Some(d) = dsu.diff(i, j) {
+                assert_eq!(x[i].op(&d), x[j]);
+            }
+        }
+    }
+}
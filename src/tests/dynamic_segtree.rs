@@ -0,0 +1,97 @@
+use crate::{dynamic_segtree::*, monoids::Sum, simple_rng::*};
+
+#[test]
+fn dynamic_segtree_random() {
+    let mut rng = Rng::new(2033);
+    let base = -1_000_000_000_000_000_000;
+    for n in [1, 2, 3, 10, 37] {
+        let mut a = vec![0i64; n];
+        let mut st = DynamicSegTree::<Sum<i64>>::new(base, base + n as i64);
+        for _ in 0..200 {
+            let i = rng.range(..n);
+            let x = rng.range(0..=20);
+            assert_eq!(st.set(base + i as i64, Sum(x)).0, a[i]);
+            a[i] = x;
+
+            let l = rng.range(..=n);
+            let r = rng.range(l..=n);
+            assert_eq!(
+                st.prod(base + l as i64..base + r as i64).0,
+                a[l..r].iter().sum::<i64>()
+            );
+            assert_eq!(st.get(base + i as i64).0, x);
+
+            let x = rng.range(0..=60);
+            let mut r = l;
+            let mut sum = 0;
+            while r < n && sum + a[r] <= x {
+                sum += a[r];
+                r += 1;
+            }
+            let (r_st, sum_st) = st.max_right(base + l as i64, |s| s.0 <= x);
+            assert_eq!(
+                (r_st - base, sum_st.0),
+                (r as i64, sum),
+                "l={l}, x={x}, a={a:?}"
+            );
+        }
+        assert_eq!(st.prod(..).0, a.iter().sum::<i64>());
+    }
+
+    let mut st = DynamicSegTree::<Sum<i64>>::new(0, 1_000_000_000_000_000_000);
+    st.set(123_456_789_012_345, Sum(5));
+    st.set(999_999_999_999_999_999, Sum(7));
+    assert_eq!(st.prod(..).0, 12);
+    assert_eq!(st.prod(..=123_456_789_012_345).0, 5);
+    assert_eq!(st.max_right(0, |s| s.0 < 12).0, 999_999_999_999_999_999);
+    assert!(st.count_node() <= 2 * 61);
+
+    let mut st = DynamicSegTree::<Sum<i64>>::new(i64::MIN, i64::MAX);
+    st.set(i64::MIN, Sum(1));
+    st.set(-1, Sum(2));
+    st.set(0, Sum(3));
+    st.set(i64::MAX - 1, Sum(4));
+    assert_eq!(st.prod(..).0, 10);
+    assert_eq!(st.prod(..0).0, 3);
+    assert_eq!(st.prod(-1..i64::MAX - 1).0, 5);
+    assert_eq!(st.get(i64::MAX - 1).0, 4);
+    assert_eq!(st.max_right(i64::MIN, |s| s.0 < 10), (i64::MAX - 1, Sum(6)));
+}
+
+#[test]
+fn persistent_segtree_random() {
+    let mut rng = Rng::new(2033);
+    let n = 30;
+    let mut st = PersistentSegTree::<Sum<i64>>::new(-5, n as i64 - 5);
+    let mut versions = vec![(st.empty(), vec![0i64; n])];
+    for _ in 0..300 {
+        let (root, a) = &versions[rng.range(..versions.len())];
+        let i = rng.range(..n);
+        let x = rng.range(0..=20);
+        let mut a = a.clone();
+        a[i] = x;
+        let root = st.set(*root, i as i64 - 5, Sum(x));
+        versions.push((root, a));
+
+        for (root, a) in &versions[versions.len().saturating_sub(5)..] {
+            let l = rng.range(..=n);
+            let r = rng.range(l..=n);
+            assert_eq!(
+                st.prod(*root, l as i64 - 5..r as i64 - 5).0,
+                a[l..r].iter().sum::<i64>()
+            );
+            let j = rng.range(..n);
+            assert_eq!(st.get(*root, j as i64 - 5).0, a[j]);
+
+            let x = rng.range(0..=60);
+            let mut r = l;
+            let mut sum = 0;
+            while r < n && sum + a[r] <= x {
+                sum += a[r];
+                r += 1;
+            }
+            let (r_st, sum_st) = st.max_right(*root, l as i64 - 5, |s| s.0 <= x);
+            assert_eq!((r_st + 5, sum_st.0), (r as i64, sum));
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{eertree::*, simple_rng::*};
+
+fn node_of(t: &Eertree, p: &[u8]) -> Option<usize> {
+    let mut v = if p.len() % 2 == 1 { 0 } else { 1 };
+    for &c in &p[p.len() / 2..] {
+        v = t.transition(v, c)?;
+    }
+    Some(v)
+}
+
+#[test]
+fn eertree_random() {
+    let mut rng = Rng::new(2044);
+    for n in [0, 1, 2, 10, 40] {
+        for sigma in [1, 2, 3] {
+            let s: Vec<u8> = (0..n).map(|_| b'a' + rng.range(0..sigma) as u8).collect();
+            let is_pal = |p: &[u8]| p.iter().eq(p.iter().rev());
+            let mut t = Eertree::new();
+            assert_eq!((t.len(0), t.len(1)), (None, Some(0)));
+            let mut pals: Vec<&[u8]> = vec![];
+            for i in 0..n {
+                let v = t.push(s[i]);
+                let longest = (0..=i).find(|&j| is_pal(&s[j..=i])).unwrap();
+                assert_eq!(t.len(v), Some(i + 1 - longest));
+                pals.extend((0..=i).map(|j| &s[j..=i]).filter(|p| is_pal(p)));
+            }
+            let mut distinct = pals.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(t.distinct_palindromes(), distinct.len());
+
+            let cnt = t.counts();
+            for p in &distinct {
+                let v = node_of(&t, p).unwrap();
+                assert_eq!(t.len(v), Some(p.len()));
+                assert_eq!(cnt[v], pals.iter().filter(|q| q == &p).count());
+                let w = t.link(v);
+                if w >= 2 {
+                    assert!(t.len(w).unwrap() < p.len());
+                    assert!(is_pal(&p[p.len() - t.len(w).unwrap()..]));
+                }
+            }
+        }
+    }
+}
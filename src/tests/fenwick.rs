@@ -0,0 +1,106 @@
+use crate::{fenwick::*, simple_rng::*};
+
+#[test]
+fn fenwick_random() {
+    let mut rng = Rng::new(2036);
+    for n in [0, 1, 2, 7, 16, 33] {
+        let mut a: Vec<i64> = (0..n).map(|_| rng.range(0..10)).collect();
+        let mut fw: Fenwick<i64> = a.clone().into();
+        for _ in 0..200 {
+            let l = rng.range(..=n);
+            let r = rng.range(l..=n);
+            assert_eq!(fw.sum(l..r), a[l..r].iter().sum::<i64>());
+            assert_eq!(fw.prefix(r), a[..r].iter().sum::<i64>());
+
+            let w = rng.range(-1..=a.iter().sum::<i64>() + 1);
+            let mut i = 0;
+            let mut acc = 0;
+            while i < n && acc + a[i] < w {
+                acc += a[i];
+                i += 1;
+            }
+            assert_eq!(fw.lower_bound(&w), i, "w={w}, a={a:?}");
+
+            if n > 0 {
+                let i = rng.range(..n);
+                let x = rng.range(0..10);
+                if rng.range(0..2) == 0 {
+                    fw.add(i, x);
+                    a[i] += x;
+                } else {
+                    fw.set(i, x);
+                    a[i] = x;
+                }
+                assert_eq!(fw.get(i), a[i]);
+            }
+        }
+    }
+}
+
+#[test]
+fn range_add_fenwick_random() {
+    let mut rng = Rng::new(2036);
+    for n in [0, 1, 2, 7, 16, 33] {
+        let mut a: Vec<i64> = (0..n).map(|_| rng.range(-10..10)).collect();
+        let mut fw: RangeAddFenwick<i64> = a.clone().into();
+        for _ in 0..200 {
+            let l = rng.range(..=n);
+            let r = rng.range(l..=n);
+            let x = rng.range(-10..10);
+            fw.add(l..r, x);
+            for a in &mut a[l..r] {
+                *a += x;
+            }
+
+            let l = rng.range(..=n);
+            let r = rng.range(l..=n);
+            assert_eq!(fw.sum(l..r), a[l..r].iter().sum::<i64>());
+            if n > 0 {
+                let i = rng.range(..n);
+                assert_eq!(fw.get(i), a[i]);
+            }
+        }
+    }
+}
+
+#[test]
+fn fenwick_multiset_random() {
+    let mut rng = Rng::new(2036);
+    let n = 20;
+    let mut set = FenwickMultiset::new(n);
+    let mut naive: Vec<usize> = vec![];
+    for _ in 0..1000 {
+        let x = rng.range(..n);
+        if rng.range(0..3) == 0 {
+            let pos = naive.iter().position(|&y| y == x);
+            assert_eq!(set.remove_one(x), pos.is_some());
+            if let Some(pos) = pos {
+                naive.remove(pos);
+            }
+        } else {
+            set.insert(x);
+            naive.push(x);
+        }
+        naive.sort();
+        assert_eq!(set.len(), naive.len());
+        assert_eq!(set.count(x), naive.iter().filter(|&&y| y == x).count());
+        assert_eq!(set.count_lt(x), naive.partition_point(|&y| y < x));
+        let k = rng.range(..=naive.len());
+        assert_eq!(set.kth(k), naive.get(k).copied());
+    }
+}
+
+#[test]
+fn inversions_random() {
+    let mut rng = Rng::new(2036);
+    for n in 0..30 {
+        let a: Vec<u32> = (0..n).map(|_| rng.range(0..8)).collect();
+        let mut naive = 0;
+        for i in 0..n {
+            for j in i + 1..n {
+                naive += (a[i] > a[j]) as u64;
+            }
+        }
+        assert_eq!(inversions(&a), naive);
+    }
+}
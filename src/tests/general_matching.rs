@@ -0,0 +1,58 @@
+use crate::{adj_list::*, general_matching::*, simple_rng::*};
+
+#[test]
+fn general_matching_random() {
+    let mut rng = Rng::new(2027);
+    for _ in 0..200 {
+        let n = rng.range(0..=10);
+        let mut edges = vec![];
+        if n > 0 {
+            for _ in 0..rng.range(0..=20) {
+                edges.push((rng.range(..n), rng.range(..n)));
+            }
+        }
+        let g = AdjList::from_biedges(n, edges.iter().copied());
+
+        let mut adj = vec![0u32; n];
+        for &(u, v) in &edges {
+            if u != v {
+                adj[u] |= 1 << v;
+                adj[v] |= 1 << u;
+            }
+        }
+        let mut dp = vec![0; 1 << n];
+        for s in 1usize..1 << n {
+            let u = s.trailing_zeros() as usize;
+            let t = s & !(1 << u);
+            dp[s] = dp[t];
+            let mut c = adj[u] as usize & t;
+            while c != 0 {
+                let v = c.trailing_zeros() as usize;
+                dp[s] = dp[s].max(dp[t & !(1 << v)] + 1);
+                c &= c - 1;
+            }
+        }
+        let best = dp[(1 << n) - 1];
+
+        let mate = general_matching(&g);
+        let mut size = 0;
+        for u in 0..n {
+            let v = mate[u];
+            if v != !0 {
+                assert_eq!(mate[v], u);
+                assert!(adj[u] >> v & 1 == 1, "edges={edges:?}, mate={mate:?}");
+                size += 1;
+            }
+        }
+        assert_eq!(size / 2, best, "edges={edges:?}, mate={mate:?}");
+
+        assert_eq!(general_matching_size(&g, &mut rng), best, "edges={edges:?}");
+    }
+}
+
+#[test]
+fn general_matching_odd_cycle() {
+    let g = AdjList::from_biedges(6, [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (2, 5)]);
+    let mate = general_matching(&g);
+    assert_eq!(mate.iter().filter(|&&v| v != !0).count(), 6);
+}
@@ -84,3 +84,71 @@ impl Monoid for A {
         A(a.wrapping_mul(c), a.wrapping_mul(d).wrapping_add(b))
     }
 }
+
+#[test]
+fn bisect_and_point_access() {
+    let mut rng = Rng::new(20260131);
+    for n in 1..=40 {
+        for _ in 0..10 {
+            let mut a: Vec<u32> = (0..n).map(|_| rng.next_u32() % 16).collect();
+            let mut st: LazySegTree<M, A> = a.iter().map(|&a| M(a, 1)).collect::<Vec<_>>().into();
+            for _ in 0..20 {
+                let f = A(rng.next_u32() % 2, rng.next_u32() % 8);
+                match rng.next_u32() % 4 {
+                    0 => {
+                        let i = (rng.next_u32() as usize) % n;
+                        a[i] = f.map(&M(a[i], 1)).0;
+                        st.apply_at(i, f);
+                    }
+                    1 => {
+                        let i = (rng.next_u32() as usize) % n;
+                        a[i] += 3;
+                        st.update(i, |x| x.0 += 3);
+                    }
+                    _ => {
+                        let l = (rng.next_u32() as usize) % (n + 1);
+                        let r = (rng.next_u32() as usize) % (n + 1);
+                        let (l, r) = if l <= r { (l, r) } else { (r, l) };
+                        for x in &mut a[l..r] {
+                            *x = f.map(&M(*x, 1)).0;
+                        }
+                        st.apply(l..r, f);
+                    }
+                }
+
+                for (i, &x) in a.iter().enumerate() {
+                    assert_eq!(st.get(i), M(x, 1), "i={i}, a={a:?}");
+                }
+
+                let l = (rng.next_u32() as usize) % (n + 1);
+                let x = rng.next_u32() % (16 * (n - l).max(1) as u32);
+                let mut r = l;
+                let mut sum = 0;
+                while r < n && sum + a[r] <= x {
+                    sum += a[r];
+                    r += 1;
+                }
+                assert_eq!(
+                    st.max_right(l, |&M(sum, _)| sum <= x),
+                    (r, M(sum, (r - l) as u32)),
+                    "l={l}, a={a:?}, x={x}"
+                );
+
+                let r = l;
+                let mut k = r;
+                let mut sum = 0;
+                while k > 0 && sum + a[k - 1] <= x {
+                    k -= 1;
+                    sum += a[k];
+                }
+                assert_eq!(
+                    st.min_left(r, |&M(sum, _)| sum <= x),
+                    (k, M(sum, (r - k) as u32)),
+                    "r={r}, a={a:?}, x={x}"
+                );
+            }
+            let v: Vec<u32> = st.to_vec().iter().map(|m| m.0).collect();
+            assert_eq!(v, a);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{cht::*, li_chao::*, simple_rng::*};
+
+#[test]
+fn li_chao_random() {
+    let mut rng = Rng::new(2049);
+    for _ in 0..100 {
+        let xs: Vec<i64> = (0..rng.range(0..20))
+            .map(|_| rng.range(-100..=100))
+            .collect();
+        let mut tree = LiChaoTree::new(xs.clone());
+        let mut segments: Vec<(i64, i64, Line<i64>)> = vec![];
+        for _ in 0..30 {
+            let l = line(rng.range(-100..=100), rng.range(-10000..=10000));
+            if rng.range(0..2) == 0 {
+                tree.add_line(l);
+                segments.push((i64::MIN, i64::MAX, l));
+            } else {
+                let a = rng.range(-110..=110);
+                let b = rng.range(a..=110);
+                tree.add_segment(a..b, l);
+                segments.push((a, b, l));
+            }
+            for &x in tree.xs() {
+                let naive = segments
+                    .iter()
+                    .filter(|&&(a, b, _)| a <= x && x < b)
+                    .map(|(_, _, l)| l.y(x))
+                    .min();
+                assert_eq!(tree.y_min(x), naive);
+            }
+        }
+        let mut sorted = xs;
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(tree.xs(), sorted);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{
+    lazy_segtree::{LazySegTree, Map},
+    modint::*,
+    monoids::*,
+    segtree::{Monoid, SegTree},
+    simple_rng::*,
+};
+
+fn fold<T: Monoid>(a: &[T]) -> T {
+    a.iter().fold(T::id(), |acc, x| acc.op(x))
+}
+
+#[test]
+fn segtree_monoids() {
+    let mut rng = Rng::new(2032);
+    for n in 1..=30 {
+        let a: Vec<i64> = (0..n).map(|_| rng.range(-50..=50)).collect();
+        let sum: SegTree<_> = a.iter().map(|&x| Sum(x)).collect::<Vec<_>>().into();
+        let min: SegTree<_> = a.iter().map(|&x| Min(x)).collect::<Vec<_>>().into();
+        let max: SegTree<_> = a.iter().map(|&x| Max(x)).collect::<Vec<_>>().into();
+        let gcd: SegTree<_> = a.iter().map(|&x| Gcd(x)).collect::<Vec<_>>().into();
+        let xor: SegTree<_> = a.iter().map(|&x| Xor(x)).collect::<Vec<_>>().into();
+        let and: SegTree<_> = a.iter().map(|&x| And(x)).collect::<Vec<_>>().into();
+        let or: SegTree<_> = a.iter().map(|&x| Or(x)).collect::<Vec<_>>().into();
+        let mss: SegTree<_> = a
+            .iter()
+            .map(|&x| MaxSubarray::new(x))
+            .collect::<Vec<_>>()
+            .into();
+        let argmin: SegTree<_> = a
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| ArgMin { value, index })
+            .collect::<Vec<_>>()
+            .into();
+        let argmax: SegTree<_> = a
+            .iter()
+            .enumerate()
+            .map(|(index, &value)| ArgMax { value, index })
+            .collect::<Vec<_>>()
+            .into();
+        for l in 0..n {
+            for r in l + 1..=n {
+                let s = &a[l..r];
+                assert_eq!(sum.prod(l..r).0, s.iter().sum::<i64>());
+                assert_eq!(min.prod(l..r).0, *s.iter().min().unwrap());
+                assert_eq!(max.prod(l..r).0, *s.iter().max().unwrap());
+                assert_eq!(
+                    gcd.prod(l..r).0,
+                    s.iter().fold(0, |g, &x| crate::integer::gcd(g, x))
+                );
+                assert_eq!(xor.prod(l..r).0, s.iter().fold(0, |g, &x| g ^ x));
+                assert_eq!(and.prod(l..r).0, s.iter().fold(!0, |g, &x| g & x));
+                assert_eq!(or.prod(l..r).0, s.iter().fold(0, |g, &x| g | x));
+                let best = (0..s.len())
+                    .flat_map(|i| (i + 1..=s.len()).map(move |j| s[i..j].iter().sum::<i64>()))
+                    .max()
+                    .unwrap();
+                assert_eq!(mss.prod(l..r).best, best, "s={s:?}");
+                let i = (l..r).min_by_key(|&i| (a[i], i)).unwrap();
+                assert_eq!(argmin.prod(l..r).index, i);
+                let i = (l..r).max_by_key(|&i| (a[i], !i)).unwrap();
+                assert_eq!(argmax.prod(l..r).index, i);
+            }
+        }
+    }
+}
+
+#[test]
+fn segtree_affine_modint() {
+    const M: u32 = 998244353;
+    let mut rng = Rng::new(2032);
+    let fs: Vec<Affine<ModInt<M>>> = (0..20)
+        .map(|_| Affine::new(mint(rng.range(0..M)), mint(rng.range(0..M))))
+        .collect();
+    let st: SegTree<_> = fs.clone().into();
+    let x = mint(12345);
+    for l in 0..=fs.len() {
+        for r in l..=fs.len() {
+            let y = fs[l..r].iter().fold(x, |x, f| f.apply(x));
+            assert_eq!(st.prod(l..r).apply(x), y);
+            assert_eq!(fold(&fs[l..r]).apply(x), y);
+        }
+    }
+}
+
+fn lazy_random<T, M>(
+    rng: &mut Rng,
+    init: impl Fn(i64) -> T,
+    map: impl Fn(&mut Rng) -> (M, Box<dyn Fn(i64) -> i64>),
+    get: impl Fn(&T) -> i64,
+    naive: impl Fn(&[i64]) -> i64,
+) where
+    T: crate::lazy_segtree::Monoid,
+    M: Map<T>,
+{
+    for n in 1..=20 {
+        let mut a: Vec<i64> = (0..n).map(|_| rng.range(-50..=50)).collect();
+        let mut st: LazySegTree<T, M> = a.iter().map(|&x| init(x)).collect::<Vec<_>>().into();
+        for _ in 0..30 {
+            let l = rng.range(..n);
+            let r = rng.range(l + 1..=n);
+            let (m, f) = map(rng);
+            for x in &mut a[l..r] {
+                *x = f(*x);
+            }
+            st.apply(l..r, m);
+            let l = rng.range(..n);
+            let r = rng.range(l + 1..=n);
+            assert_eq!(get(&st.prod(l..r)), naive(&a[l..r]), "a={a:?}");
+        }
+    }
+}
+
+#[test]
+fn lazy_actions() {
+    let mut rng = Rng::new(2032);
+    let sum = |a: &[i64]| a.iter().sum::<i64>();
+    let min = |a: &[i64]| *a.iter().min().unwrap();
+    let max = |a: &[i64]| *a.iter().max().unwrap();
+
+    lazy_random(
+        &mut rng,
+        SumLen::new,
+        |rng| {
+            let c = rng.range(-5..=5);
+            (RangeAdd(c), Box::new(move |x| x + c))
+        },
+        |x| x.sum,
+        sum,
+    );
+    lazy_random(
+        &mut rng,
+        SumLen::new,
+        |rng| {
+            let c = rng.range(-5..=5);
+            (RangeAssign(Some(c)), Box::new(move |_| c))
+        },
+        |x| x.sum,
+        sum,
+    );
+    lazy_random(
+        &mut rng,
+        SumLen::new,
+        |rng| {
+            let (a, b) = (rng.range(-1..=1), rng.range(-5..=5));
+            (Affine::new(a, b), Box::new(move |x| a * x + b))
+        },
+        |x| x.sum,
+        sum,
+    );
+    lazy_random(
+        &mut rng,
+        Min,
+        |rng| {
+            let c = rng.range(-5..=5);
+            (RangeAdd(c), Box::new(move |x| x + c))
+        },
+        |x| x.0,
+        min,
+    );
+    lazy_random(
+        &mut rng,
+        Max,
+        |rng| {
+            let c = rng.range(-50..=50);
+            (RangeAssign(Some(c)), Box::new(move |_| c))
+        },
+        |x| x.0,
+        max,
+    );
+    lazy_random(
+        &mut rng,
+        Max,
+        |rng| {
+            let c = rng.range(-50..=50);
+            (Chmin(c), Box::new(move |x: i64| x.min(c)))
+        },
+        |x| x.0,
+        max,
+    );
+    lazy_random(
+        &mut rng,
+        Min,
+        |rng| {
+            let c = rng.range(-50..=50);
+            (Chmax(c), Box::new(move |x: i64| x.max(c)))
+        },
+        |x| x.0,
+        min,
+    );
+    lazy_random(
+        &mut rng,
+        Max,
+        |rng| {
+            let c = rng.range(-5..=5);
+            (RangeAdd(c), Box::new(move |x| x + c))
+        },
+        |x| x.0,
+        max,
+    );
+    lazy_random(
+        &mut rng,
+        Min,
+        |rng| {
+            let c = rng.range(-50..=50);
+            (RangeAssign(Some(c)), Box::new(move |_| c))
+        },
+        |x| x.0,
+        min,
+    );
+    lazy_random(
+        &mut rng,
+        Min,
+        |rng| {
+            let c = rng.range(-50..=50);
+            (Chmin(c), Box::new(move |x: i64| x.min(c)))
+        },
+        |x| x.0,
+        min,
+    );
+    lazy_random(
+        &mut rng,
+        Max,
+        |rng| {
+            let c = rng.range(-50..=50);
+            (Chmax(c), Box::new(move |x: i64| x.max(c)))
+        },
+        |x| x.0,
+        max,
+    );
+}
+
+fn preserves_id<T, M>(m: M)
+where
+    T: crate::lazy_segtree::Monoid + PartialEq + std::fmt::Debug,
+    M: Map<T>,
+{
+    assert_eq!(m.map(&<T as crate::lazy_segtree::Monoid>::id()), T::id());
+}
+
+#[test]
+fn maps_preserve_id() {
+    for c in [-5i64, 0, 5] {
+        preserves_id::<SumLen<i64>, _>(RangeAdd(c));
+        preserves_id::<Min<i64>, _>(RangeAdd(c));
+        preserves_id::<Max<i64>, _>(RangeAdd(c));
+        preserves_id::<SumLen<i64>, _>(RangeAssign(Some(c)));
+        preserves_id::<Min<i64>, _>(RangeAssign(Some(c)));
+        preserves_id::<Max<i64>, _>(RangeAssign(Some(c)));
+        preserves_id::<SumLen<i64>, _>(Affine::new(c, c));
+        preserves_id::<Min<i64>, _>(Chmin(c));
+        preserves_id::<Max<i64>, _>(Chmin(c));
+        preserves_id::<Min<i64>, _>(Chmax(c));
+        preserves_id::<Max<i64>, _>(Chmax(c));
+    }
+}
+
+#[test]
+fn gcd_extremes() {
+    assert_eq!(i64::MIN.gcd(6), 2);
+    assert_eq!(i64::MIN.gcd(-1), 1);
+    assert_eq!((-12i32).gcd(i32::MIN), 4);
+    assert_eq!(i8::MIN.gcd(2), 2);
+    assert_eq!(u64::MAX.gcd(3), 3);
+    let st: SegTree<_> = vec![Gcd(i64::MIN), Gcd(-12), Gcd(18)].into();
+    assert_eq!(st.prod(..).0, 2);
+    assert_eq!(st.prod(..2).0, 4);
+}
+
+#[test]
+#[should_panic(expected = "gcd overflow")]
+fn gcd_overflow() {
+    i8::MIN.gcd(0);
+}
@@ -0,0 +1,99 @@
+use crate::{
+    monoids::{Min, Sum},
+    segtree_2d::*,
+    simple_rng::*,
+};
+
+#[test]
+fn range_tree_2d_random() {
+    let mut rng = Rng::new(2035);
+    for n in [0, 1, 2, 5, 16, 37] {
+        let points: Vec<(i64, i64)> = (0..n)
+            .map(|_| (rng.range(-10..=10), rng.range(-10..=10)))
+            .collect();
+        let mut a: Vec<i64> = (0..n).map(|_| rng.range(0..100)).collect();
+        let mut st: RangeTree2d<Sum<i64>> = points
+            .iter()
+            .zip(&a)
+            .map(|(&(x, y), &v)| (x, y, Sum(v)))
+            .collect::<Vec<_>>()
+            .into();
+        let mut mt = RangeTree2d::<Min<i64>>::new(&points);
+        for (i, &v) in a.iter().enumerate() {
+            mt.set(i, Min(v));
+        }
+        for _ in 0..200 {
+            let x0 = rng.range(-12..=12);
+            let x1 = rng.range(x0..=12);
+            let y0 = rng.range(-12..=12);
+            let y1 = rng.range(y0..=12);
+            let inside = |i: usize| {
+                let (x, y) = points[i];
+                x0 <= x && x < x1 && y0 <= y && y <= y1
+            };
+            assert_eq!(
+                st.prod(x0..x1, y0..=y1).0,
+                (0..n).filter(|&i| inside(i)).map(|i| a[i]).sum::<i64>()
+            );
+            assert_eq!(
+                mt.prod(x0..x1, y0..=y1).0,
+                (0..n)
+                    .filter(|&i| inside(i))
+                    .map(|i| a[i])
+                    .min()
+                    .unwrap_or(i64::MAX)
+            );
+            assert_eq!(st.prod(.., ..).0, a.iter().sum::<i64>());
+
+            if n > 0 {
+                let i = rng.range(..n);
+                let v = rng.range(0..100);
+                assert_eq!(st.set(i, Sum(v)).0, a[i]);
+                mt.set(i, Min(v));
+                a[i] = v;
+                assert_eq!(st.get(i).0, v);
+            }
+        }
+    }
+}
+
+#[test]
+fn segtree_2d_random() {
+    let mut rng = Rng::new(2035);
+    for (h, w) in [(1, 1), (1, 7), (5, 1), (6, 9), (8, 8)] {
+        let mut a: Vec<Vec<i64>> = (0..h)
+            .map(|_| (0..w).map(|_| rng.range(0..100)).collect())
+            .collect();
+        let mut st: SegTree2d<Sum<i64>> = a
+            .iter()
+            .map(|row| row.iter().map(|&x| Sum(x)).collect())
+            .collect::<Vec<_>>()
+            .into();
+        let mut mt = SegTree2d::<Min<i64>>::new(h, w);
+        for (i, row) in a.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                mt.set(i, j, Min(x));
+            }
+        }
+        for _ in 0..200 {
+            let t = rng.range(..=h);
+            let b = rng.range(t..=h);
+            let l = rng.range(..=w);
+            let r = rng.range(l..=w);
+            let cells = || a[t..b].iter().flat_map(|row| &row[l..r]);
+            assert_eq!(st.prod(t..b, l..r).0, cells().sum::<i64>());
+            assert_eq!(
+                mt.prod(t..b, l..r).0,
+                cells().copied().min().unwrap_or(i64::MAX)
+            );
+
+            let i = rng.range(..h);
+            let j = rng.range(..w);
+            let v = rng.range(0..100);
+            assert_eq!(st.set(i, j, Sum(v)).0, a[i][j]);
+            mt.set(i, j, Min(v));
+            a[i][j] = v;
+            assert_eq!(st.get(i, j).0, v);
+        }
+    }
+}
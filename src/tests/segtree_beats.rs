@@ -0,0 +1,111 @@
+use crate::{segtree_beats::*, simple_rng::*};
+
+#[test]
+fn chmin_chmax_add_sum() {
+    let mut rng = Rng::new(2034);
+    for n in 1..=40 {
+        for _ in 0..5 {
+            let mut a: Vec<i64> = (0..n).map(|_| rng.range(-20..=20)).collect();
+            let mut st: SegTreeBeats<ChminChmaxSum, ChminChmaxAdd> = a
+                .iter()
+                .map(|&x| ChminChmaxSum::new(x))
+                .collect::<Vec<_>>()
+                .into();
+            for _ in 0..100 {
+                let l = rng.range(..=n);
+                let r = rng.range(l..=n);
+                let x = rng.range(-20..=20);
+                let (f, g): (_, fn(i64, i64) -> i64) = match rng.range(0..5) {
+                    0 => (ChminChmaxAdd::chmin(x), |a, x| a.min(x)),
+                    1 => (ChminChmaxAdd::chmax(x), |a, x| a.max(x)),
+                    2 => (ChminChmaxAdd::add(x / 4), |a, x| a + x / 4),
+                    3 => (ChminChmaxAdd::assign(x), |_, x| x),
+                    _ => {
+                        let i = rng.range(..n);
+                        a[i] = x;
+                        st.set(i, ChminChmaxSum::new(x));
+                        continue;
+                    }
+                };
+                for a in &mut a[l..r] {
+                    *a = g(*a, x);
+                }
+                st.apply(l..r, f);
+
+                let l = rng.range(..n);
+                let r = rng.range(l + 1..=n);
+                let p = st.prod(l..r);
+                assert_eq!(p.sum, a[l..r].iter().sum::<i64>(), "a={a:?}");
+                assert_eq!(p.min, *a[l..r].iter().min().unwrap(), "a={a:?}");
+                assert_eq!(p.max, *a[l..r].iter().max().unwrap(), "a={a:?}");
+                let i = rng.range(..n);
+                assert_eq!(st.get(i).sum, a[i]);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SumMax(u64, u64, usize);
+
+impl crate::lazy_segtree::Monoid for SumMax {
+    fn id() -> Self {
+        SumMax(0, 0, 0)
+    }
+    fn op(&self, other: &Self) -> Self {
+        SumMax(self.0 + other.0, self.1.max(other.1), self.2 + other.2)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Mod(u64);
+
+impl BeatsMap<SumMax> for Mod {
+    fn id() -> Self {
+        Mod(u64::MAX)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        *other
+    }
+    fn map(&self, x: &SumMax) -> Option<SumMax> {
+        if x.1 < self.0 {
+            Some(*x)
+        } else if x.2 == 1 {
+            Some(SumMax(x.0 % self.0, x.1 % self.0, 1))
+        } else {
+            None
+        }
+    }
+    fn is_break(&self, x: &SumMax) -> bool {
+        x.1 < self.0
+    }
+}
+
+#[test]
+fn custom_range_mod() {
+    let mut rng = Rng::new(2034);
+    let n = 50;
+    let mut a: Vec<u64> = (0..n).map(|_| rng.range(0..1000)).collect();
+    let mut st: SegTreeBeats<SumMax, Mod> = a
+        .iter()
+        .map(|&x| SumMax(x, x, 1))
+        .collect::<Vec<_>>()
+        .into();
+    for _ in 0..200 {
+        let l = rng.range(..n);
+        let r = rng.range(l + 1..=n);
+        let m = rng.range(1..100);
+        for a in &mut a[l..r] {
+            *a %= m;
+        }
+        st.apply(l..r, Mod(m));
+        let l = rng.range(..n);
+        let r = rng.range(l + 1..=n);
+        assert_eq!(st.prod(l..r).0, a[l..r].iter().sum::<u64>());
+        if rng.range(0..4) == 0 {
+            let i = rng.range(..n);
+            a[i] = rng.range(0..1000);
+            st.set(i, SumMax(a[i], a[i], 1));
+        }
+    }
+}
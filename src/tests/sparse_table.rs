@@ -0,0 +1,70 @@
+use crate::{cartesian_tree::*, monoids::*, segtree::Monoid, simple_rng::*, sparse_table::*};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Cat(Vec<u32>);
+
+impl Monoid for Cat {
+    fn id() -> Self {
+        Cat(vec![])
+    }
+    fn op(&self, other: &Self) -> Self {
+        Cat([&self.0[..], &other.0[..]].concat())
+    }
+}
+
+#[test]
+fn sparse_table_random() {
+    let mut rng = Rng::new(2037);
+    for n in [0, 1, 2, 3, 8, 9, 31] {
+        let a: Vec<i32> = (0..n).map(|_| rng.range(0..10)).collect();
+        let st: SparseTable<Min<i32>> = a.iter().map(|&x| Min(x)).collect::<Vec<_>>().into();
+        let max: SparseTable<Max<i32>> = a.iter().map(|&x| Max(x)).collect::<Vec<_>>().into();
+        let gcd: SparseTable<Gcd<i32>> = a.iter().map(|&x| Gcd(x)).collect::<Vec<_>>().into();
+        let and: SparseTable<And<i32>> = a.iter().map(|&x| And(x)).collect::<Vec<_>>().into();
+        let or: SparseTable<Or<i32>> = a.iter().map(|&x| Or(x)).collect::<Vec<_>>().into();
+        let dst: DisjointSparseTable<Cat> = a
+            .iter()
+            .map(|&x| Cat(vec![x as u32]))
+            .collect::<Vec<_>>()
+            .into();
+        for l in 0..=n {
+            for r in l..=n {
+                assert_eq!(
+                    st.prod(l..r).0,
+                    a[l..r].iter().copied().min().unwrap_or(i32::MAX)
+                );
+                let s = &a[l..r];
+                assert_eq!(
+                    max.prod(l..r).0,
+                    s.iter().copied().max().unwrap_or(i32::MIN)
+                );
+                assert_eq!(
+                    gcd.prod(l..r).0,
+                    s.iter().fold(0, |g, &x| crate::integer::gcd(g, x as i64)) as i32
+                );
+                assert_eq!(and.prod(l..r).0, s.iter().fold(!0, |g, &x| g & x));
+                assert_eq!(or.prod(l..r).0, s.iter().fold(0, |g, &x| g | x));
+                assert_eq!(
+                    dst.prod(l..r).0,
+                    a[l..r].iter().map(|&x| x as u32).collect::<Vec<_>>()
+                );
+            }
+        }
+        assert_eq!(dst.prod(..).0.len(), n);
+    }
+}
+
+#[test]
+fn sparse_table_cartesian_tree() {
+    let mut rng = Rng::new(2037);
+    let n = 40;
+    let a: Vec<i32> = (0..n).map(|_| rng.range(0..10)).collect();
+    let p = cartesian_tree_up(&a);
+    let st: SparseTable<ArgMin<i32>> = a
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| ArgMin { value, index })
+        .collect::<Vec<_>>()
+        .into();
+    assert_eq!(st.prod(..).index, p.iter().position(|&v| v == !0).unwrap());
+}
@@ -0,0 +1,109 @@
+use crate::{simple_rng::*, string::*};
+
+fn random_string(rng: &mut Rng, n: usize, sigma: u8) -> Vec<u8> {
+    (0..n)
+        .map(|_| b'a' + rng.range(0..sigma as u32) as u8)
+        .collect()
+}
+
+#[test]
+fn z_and_prefix_function_random() {
+    let mut rng = Rng::new(2043);
+    for n in 0..30 {
+        let s = random_string(&mut rng, n, 2);
+        let z = z_array(&s);
+        let pi = prefix_function(&s);
+        for i in 0..n {
+            let k = s.iter().zip(&s[i..]).take_while(|(a, b)| a == b).count();
+            assert_eq!(z[i], k);
+            let k = (0..=i).rev().find(|&k| s[..k] == s[i + 1 - k..=i]).unwrap();
+            assert_eq!(pi[i], k);
+        }
+
+        let m = rng.range(0..4);
+        let p = random_string(&mut rng, m, 2);
+        let naive: Vec<usize> = (0..=n).filter(|&i| s[i..].starts_with(&p)).collect();
+        assert_eq!(kmp_search(&p, &s), naive);
+
+        let p: Vec<usize> = p.iter().map(|&c| (c - b'a') as usize).collect();
+        let next = kmp_automaton(&p, 2);
+        let mut k = 0;
+        let mut found = vec![];
+        for (i, &c) in s.iter().enumerate() {
+            k = next[k][(c - b'a') as usize];
+            if k == p.len() {
+                found.push(i + 1 - k);
+            }
+        }
+        if !p.is_empty() {
+            assert_eq!(found, naive);
+        }
+    }
+}
+
+#[test]
+fn manacher_random() {
+    let mut rng = Rng::new(2043);
+    for n in 0..30 {
+        let s = random_string(&mut rng, n, 2);
+        let rad = manacher(&s);
+        assert_eq!(rad.len(), (2 * n).saturating_sub(1));
+        for (k, &len) in rad.iter().enumerate() {
+            let is_pal = |l: usize, r: usize| s[l..r].iter().eq(s[l..r].iter().rev());
+            let best = (0..=n)
+                .filter(|&len| len % 2 == (k + 1) % 2 && len <= k + 1 && k + 1 + len <= 2 * n + 1)
+                .filter(|&len| is_pal((k + 1 - len) / 2, (k + 1 + len) / 2))
+                .max()
+                .unwrap();
+            assert_eq!(len, best, "s={s:?}, k={k}");
+        }
+    }
+}
+
+#[test]
+fn runs_random() {
+    let mut rng = Rng::new(2043);
+    for n in 0..40 {
+        for sigma in [1, 2, 3] {
+            let s = random_string(&mut rng, n, sigma);
+            let mut naive = vec![];
+            for l in 0..n {
+                for r in l + 1..=n {
+                    let p = (1..=r - l)
+                        .find(|&p| (l + p..r).all(|i| s[i] == s[i - p]))
+                        .unwrap();
+                    let maximal =
+                        (l == 0 || s[l - 1] != s[l - 1 + p]) && (r == n || s[r] != s[r - p]);
+                    if 2 * p <= r - l && maximal {
+                        naive.push((l, r, p));
+                    }
+                }
+            }
+            assert_eq!(runs(&s), naive, "s={:?}", String::from_utf8_lossy(&s));
+        }
+    }
+}
+
+#[test]
+fn rolling_hash_random() {
+    let mut rng = Rng::new(2043);
+    let base = rng.range(2..HASH_MOD - 1);
+    let s = random_string(&mut rng, 60, 2);
+    let rh = RollingHash::new(&s, base);
+    for _ in 0..1000 {
+        let len = rng.range(0..=8);
+        let i = rng.range(..=s.len() - len);
+        let j = rng.range(..=s.len() - len);
+        assert_eq!(
+            rh.hash(i..i + len) == rh.hash(j..j + len),
+            s[i..i + len] == s[j..j + len]
+        );
+        let k = rng.range(i..=i + len);
+        assert_eq!(
+            rh.concat(rh.hash(i..k), rh.hash(k..i + len), i + len - k),
+            rh.hash(i..i + len)
+        );
+    }
+    let t: Vec<u32> = s.iter().map(|&c| c as u32).collect();
+    assert_eq!(RollingHash::new(&t, base).hash(..), rh.hash(..));
+}
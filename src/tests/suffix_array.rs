@@ -1,4 +1,4 @@
-use crate::suffix_array::*;
+use crate::{simple_rng::*, suffix_array::*};
 
 #[test]
 fn lcp_test() {
@@ -28,3 +28,86 @@ fn sa_is_test() {
         assert_eq!(sa_is, sa_naive);
     }
 }
+
+#[test]
+fn suffix_array_generic_random() {
+    let mut rng = Rng::new(2041);
+    for n in [0, 1, 2, 10, 21, 100] {
+        for max in [0, 1, 3, 1000] {
+            let s: Vec<u32> = (0..n).map(|_| rng.range(0..=max)).collect();
+            let sa = suffix_array_naive(&s);
+            assert_eq!(suffix_array_u32(&s, max), sa);
+            let t: Vec<i64> = s.iter().map(|&c| -(c as i64) * 7).collect();
+            assert_eq!(suffix_array_ord(&t), suffix_array_naive(&t));
+
+            let index = SuffixArrayIndex::new(s.clone());
+            assert_eq!(index.sa(), sa);
+            assert_eq!(index.lcp_array(), lcp_array(&s, &sa));
+            for i in 0..=n {
+                assert_eq!(index.rank().get(i).map(|&r| sa[r]), (i < n).then_some(i));
+                for j in 0..=n {
+                    let h = s[i..]
+                        .iter()
+                        .zip(&s[j..])
+                        .take_while(|(a, b)| a == b)
+                        .count();
+                    assert_eq!(index.lcp(i, j), h);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn suffix_array_index_queries() {
+    let index = SuffixArrayIndex::new(b"abracadabra".to_vec());
+    assert_eq!(index.count_occurrences(b"abra"), 2);
+    assert_eq!(index.find_all(b"a"), [0, 3, 5, 7, 10]);
+    assert_eq!(index.find_all(b"abracadabrab"), []);
+    assert_eq!(index.find_all(b""), (0..=10).collect::<Vec<_>>());
+    assert_eq!(&index.text()[index.longest_repeated_substring()], b"abra");
+    let (i, j) = longest_common_substring(b"xabcdy", b"zzbcdab");
+    assert_eq!((&b"xabcdy"[i], &b"zzbcdab"[j]), (&b"bcd"[..], &b"bcd"[..]));
+
+    let mut rng = Rng::new(2042);
+    for n in [0, 1, 5, 30] {
+        let s: Vec<u8> = (0..n).map(|_| b'a' + rng.range(0..3) as u8).collect();
+        let index = SuffixArrayIndex::new(s.clone());
+        let mut subs: Vec<&[u8]> = (0..n)
+            .flat_map(|i| (i + 1..=n).map(move |j| (i, j)))
+            .map(|(i, j)| &s[i..j])
+            .collect();
+        subs.sort();
+        subs.dedup();
+        assert_eq!(index.distinct_substrings(), subs.len());
+
+        let repeated = subs
+            .iter()
+            .filter(|p| index.find_all(p).len() >= 2)
+            .map(|p| p.len())
+            .max()
+            .unwrap_or(0);
+        assert_eq!(index.longest_repeated_substring().len(), repeated);
+
+        for _ in 0..20 {
+            let m = rng.range(0..=3);
+            let p: Vec<u8> = (0..m).map(|_| b'a' + rng.range(0..3) as u8).collect();
+            let naive: Vec<usize> = (0..n).filter(|&i| s[i..].starts_with(&p)).collect();
+            assert_eq!(index.find_all(&p), naive);
+            assert_eq!(index.count_occurrences(&p), index.find_all(&p).len());
+        }
+
+        let t: Vec<u8> = (0..rng.range(0..20))
+            .map(|_| b'a' + rng.range(0..3) as u8)
+            .collect();
+        let (i, j) = longest_common_substring(&s, &t);
+        assert_eq!(s[i.clone()], t[j]);
+        let best = subs
+            .iter()
+            .filter(|p| t.windows(p.len()).any(|w| w == **p))
+            .map(|p| p.len())
+            .max()
+            .unwrap_or(0);
+        assert_eq!(i.len(), best);
+    }
+}
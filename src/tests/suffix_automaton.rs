@@ -0,0 +1,36 @@
+use crate::{simple_rng::*, suffix_automaton::*};
+
+#[test]
+fn suffix_automaton_random() {
+    let mut rng = Rng::new(2044);
+    for n in [0, 1, 2, 10, 40] {
+        for sigma in [1, 3] {
+            let s: Vec<u8> = (0..n).map(|_| b'a' + rng.range(0..sigma) as u8).collect();
+            let sa = SuffixAutomaton::from(&s[..]);
+            assert!(sa.count_node() <= (2 * n).max(1));
+
+            let mut subs: Vec<&[u8]> = (0..n)
+                .flat_map(|i| (i + 1..=n).map(move |j| (i, j)))
+                .map(|(i, j)| &s[i..j])
+                .collect();
+            subs.sort();
+            subs.dedup();
+            assert_eq!(sa.distinct_substrings(), subs.len());
+
+            let cnt = sa.endpos_counts();
+            for p in &subs {
+                assert!(sa.contains(p));
+                let mut v = 0;
+                for &c in p.iter() {
+                    v = sa.transition(v, c).unwrap();
+                }
+                let occ = s.windows(p.len()).filter(|w| w == p).count();
+                assert_eq!(cnt[v], occ);
+                let link_len = sa.link(v).map_or(0, |u| sa.len(u));
+                assert!(link_len < p.len() && p.len() <= sa.len(v));
+            }
+            assert!(!sa.contains(b"ad"));
+            assert_eq!(sa.link(0), None);
+        }
+    }
+}
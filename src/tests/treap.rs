@@ -0,0 +1,89 @@
+use crate::{
+    lazy_segtree::{Map, Monoid},
+    simple_rng::*,
+    treap::*,
+};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Cat(Vec<u32>);
+
+impl Monoid for Cat {
+    fn id() -> Self {
+        Cat(vec![])
+    }
+    fn op(&self, other: &Self) -> Self {
+        Cat([&self.0[..], &other.0[..]].concat())
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Add(u32);
+
+impl Map<Cat> for Add {
+    fn id() -> Self {
+        Add(0)
+    }
+    fn comp(&self, other: &Self) -> Self {
+        Add(self.0 + other.0)
+    }
+    fn map(&self, x: &Cat) -> Cat {
+        Cat(x.0.iter().map(|y| y + self.0).collect())
+    }
+}
+
+#[test]
+fn treap_random() {
+    let mut rng = Rng::new(2038);
+    let mut treap = Treap::<Cat, Add>::new(Rng::new(38));
+    let mut a: Vec<u32> = (0..10).collect();
+    let mut root = treap.build(a.iter().map(|&x| Cat(vec![x])));
+    for _ in 0..2000 {
+        let n = a.len();
+        match rng.range(0..6) {
+            0 => {
+                let i = rng.range(..=n);
+                let x = rng.range(0..100);
+                treap.insert(&mut root, i, Cat(vec![x]));
+                a.insert(i, x);
+            }
+            1 if n > 0 => {
+                let i = rng.range(..n);
+                assert_eq!(treap.remove(&mut root, i).0, [a.remove(i)]);
+            }
+            2 => {
+                let l = rng.range(..=n);
+                let r = rng.range(l..=n);
+                treap.reverse(&mut root, l..r);
+                a[l..r].reverse();
+            }
+            3 => {
+                let l = rng.range(..=n);
+                let r = rng.range(l..=n);
+                let x = rng.range(0..10);
+                treap.apply(root, l..r, Add(x));
+                for a in &mut a[l..r] {
+                    *a += x;
+                }
+            }
+            4 => {
+                let k = rng.range(..=n);
+                let (left, right) = treap.split_at(root, k);
+                assert_eq!(treap.len(left), k);
+                assert_eq!(treap.prod(right, ..).0, a[k..]);
+                root = treap.merge(left, right);
+            }
+            _ if n > 0 => {
+                let i = rng.range(..n);
+                assert_eq!(treap.get(root, i).0, [a[i]]);
+            }
+            _ => {}
+        }
+        let n = a.len();
+        assert_eq!(treap.len(root), n);
+        let l = rng.range(..=n);
+        let r = rng.range(l..=n);
+        assert_eq!(treap.prod(root, l..r).0, a[l..r]);
+    }
+    let v: Vec<u32> = treap.to_vec(root).into_iter().flat_map(|c| c.0).collect();
+    assert_eq!(v, a);
+}
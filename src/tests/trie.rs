@@ -1,4 +1,6 @@
-use crate::trie::*;
+use std::collections::BTreeMap;
+
+use crate::{simple_rng::*, trie::*};
 
 #[test]
 fn trie() {
@@ -14,4 +16,99 @@ fn trie() {
     assert_eq!(links.next(), Some((b'b', 2)));
     assert_eq!(links.next(), Some((b'x', 4)));
     assert_eq!(links.next(), None);
+
+    assert!(trie.remove(b"axz".iter().copied()));
+    assert_eq!(trie.transition(1, b'x'), None);
+    assert_eq!(trie.insert_slice(b"axz"), (true, 8));
+    assert!(trie.remove(b"aba".iter().copied()));
+    assert_eq!(trie.insert_slice(b"ab"), (true, 2));
+}
+
+#[test]
+fn trie_counters_random() {
+    let mut rng = Rng::new(2046);
+    for _ in 0..50 {
+        let mut trie = Trie::new();
+        let mut model: BTreeMap<Vec<u8>, usize> = BTreeMap::new();
+        for _ in 0..200 {
+            let s: Vec<u8> = (0..rng.range(0..4))
+                .map(|_| b'a' + rng.range(0..3) as u8)
+                .collect();
+            let count = model.get(&s).copied().unwrap_or(0);
+            match rng.range(0..4) {
+                0 => {
+                    let (inserted, v) = trie.insert(s.iter().copied());
+                    assert_eq!(inserted, count == 0);
+                    assert_eq!(trie.find(s.iter().copied()), Some(v));
+                    *model.entry(s).or_default() += 1;
+                }
+                1 | 2 => {
+                    assert_eq!(trie.remove(s.iter().copied()), count > 0);
+                    if count == 1 {
+                        model.remove(&s);
+                    } else if count > 1 {
+                        *model.get_mut(&s).unwrap() -= 1;
+                    }
+                }
+                _ => {
+                    assert_eq!(trie.count(s.iter().copied()), count);
+                    assert_eq!(trie.contains(s.iter().copied()), count > 0);
+                    let prefix: usize = model
+                        .iter()
+                        .filter(|(t, _)| t.starts_with(&s))
+                        .map(|(_, &c)| c)
+                        .sum();
+                    assert_eq!(
+                        trie.find(s.iter().copied()).map(|i| trie.pass_count(i)),
+                        (prefix > 0 || s.is_empty()).then_some(prefix)
+                    );
+                    let longest = model
+                        .keys()
+                        .filter(|t| s.starts_with(t))
+                        .map(|t| t.len())
+                        .max();
+                    assert_eq!(
+                        trie.longest_prefix_match(s.iter().copied())
+                            .map(|(len, _)| len),
+                        longest
+                    );
+                }
+            }
+            assert_eq!(trie.len(), model.values().sum::<usize>());
+        }
+        let expected: Vec<(Vec<u8>, usize)> = model.into_iter().collect();
+        assert_eq!(trie.iter().collect::<Vec<_>>(), expected);
+        let mut stack = vec![0];
+        while let Some(v) = stack.pop() {
+            assert!(v == 0 || trie.pass_count(v) > 0);
+            stack.extend(trie.links(v).map(|(_, w)| w));
+        }
+    }
+}
+
+#[test]
+fn trie_xor_random() {
+    let mut rng = Rng::new(20461);
+    let mut trie = Trie::new();
+    let mut naive: Vec<u64> = vec![];
+    for _ in 0..500 {
+        let x = rng.range(0..64) as u64;
+        match rng.range(0..3) {
+            0 => {
+                trie.insert(bits(x, 6));
+                naive.push(x);
+            }
+            1 => {
+                let pos = naive.iter().position(|&y| y == x);
+                assert_eq!(trie.remove(bits(x, 6)), pos.is_some());
+                if let Some(pos) = pos {
+                    naive.swap_remove(pos);
+                }
+            }
+            _ => {
+                assert_eq!(trie.xor_max(x, 6), naive.iter().map(|&y| x ^ y).max());
+                assert_eq!(trie.xor_min(x, 6), naive.iter().map(|&y| x ^ y).min());
+            }
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::two_sat::*;
+use crate::{simple_rng::*, two_sat::*};
 
 #[test]
 fn test() {
@@ -6,3 +6,58 @@ fn test() {
     ts.clause(0, true, 0, true);
     assert_eq!(ts.solve(), Some(vec![true]));
 }
+
+#[test]
+fn incremental() {
+    let mut ts = TwoSat::new(2);
+    ts.equal(0, true, 1, false);
+    let res = ts.solve().unwrap();
+    assert_ne!(res[0], res[1]);
+    assert_eq!(ts.solve_with(&[(0, true)]), Some(vec![true, false]));
+    assert_eq!(ts.solve_with(&[(0, true), (1, true)]), None);
+    ts.set_true(1, true);
+    assert_eq!(ts.solve(), Some(vec![false, true]));
+    ts.implies(1, true, 0, true);
+    assert_eq!(ts.solve(), None);
+}
+
+#[test]
+fn at_most_one_random() {
+    let mut rng = Rng::new(2028);
+    for _ in 0..200 {
+        let n = rng.range(1..=6);
+        let mut ts = TwoSat::new(n);
+        let mut clauses = vec![];
+        for _ in 0..rng.range(0..=4) {
+            let c = (
+                rng.range(..n),
+                rng.range(0..2) == 1,
+                rng.range(..n),
+                rng.range(0..2) == 1,
+            );
+            ts.clause(c.0, c.1, c.2, c.3);
+            clauses.push(c);
+        }
+        let lits: Vec<(usize, bool)> = (0..rng.range(0..=n))
+            .map(|_| (rng.range(..n), rng.range(0..2) == 1))
+            .collect();
+        let aux = ts.at_most_one(&lits);
+        assert_eq!(aux, n..n + lits.len());
+        assert_eq!(ts.len(), n + aux.len());
+
+        let ok = |x: usize| {
+            let v = |i: usize, f: bool| (x >> i & 1 == 1) == f;
+            clauses.iter().all(|&(i, f, j, g)| v(i, f) || v(j, g))
+                && lits.iter().filter(|&&(i, f)| v(i, f)).count() <= 1
+        };
+        let exists = (0usize..1 << n).any(ok);
+        match ts.solve() {
+            Some(res) => {
+                assert_eq!(res.len(), n + aux.len());
+                let x = (0..n).map(|i| (res[i] as usize) << i).sum();
+                assert!(ok(x), "clauses={clauses:?}, lits={lits:?}, res={res:?}");
+            }
+            None => assert!(!exists, "clauses={clauses:?}, lits={lits:?}"),
+        }
+    }
+}
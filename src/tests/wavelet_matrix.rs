@@ -0,0 +1,55 @@
+use crate::{monoids::Sum, simple_rng::*, wavelet_matrix::*};
+
+#[test]
+fn wavelet_matrix_random() {
+    let mut rng = Rng::new(2040);
+    for (n, max) in [(0, 1), (1, 0), (10, 0), (30, 8), (50, 1000), (20, u64::MAX)] {
+        let a: Vec<u64> = (0..n).map(|_| rng.range(0..=max)).collect();
+        let wm = WaveletMatrix::new(&a);
+        let mut wwm = WeightedWaveletMatrix::<Sum<u128>>::new(&a);
+        for (i, &x) in a.iter().enumerate() {
+            wwm.set_weight(i, Sum(x as u128));
+        }
+        for (i, &x) in a.iter().enumerate() {
+            assert_eq!(wm.get(i), x);
+        }
+        for _ in 0..100 {
+            let l = rng.range(..=n);
+            let r = rng.range(l..=n);
+            let mut sorted = a[l..r].to_vec();
+            sorted.sort();
+            let k = rng.range(..=r - l);
+            assert_eq!(wm.kth_smallest(l..r, k), sorted.get(k).copied());
+            assert_eq!(wm.kth_largest(l..r, k), sorted.iter().rev().nth(k).copied());
+
+            let lo = rng.range(0..=max);
+            let hi = rng.range(lo..=max);
+            let inside = || sorted.iter().filter(|&&x| lo <= x && x <= hi);
+            assert_eq!(wm.count(l..r, lo..=hi), inside().count());
+            assert_eq!(
+                wm.count(l..r, lo..),
+                sorted.iter().filter(|&&x| lo <= x).count()
+            );
+            assert_eq!(
+                wwm.prod(l..r, lo..=hi).0,
+                inside().map(|&x| x as u128).sum::<u128>()
+            );
+            assert_eq!(
+                wm.prev_value(l..r, lo),
+                sorted.iter().rev().find(|&&x| x < lo).copied()
+            );
+            assert_eq!(
+                wm.next_value(l..r, lo),
+                sorted.iter().find(|&&x| x >= lo).copied()
+            );
+        }
+        if n > 0 {
+            let i = rng.range(..n);
+            wwm.set_weight(i, Sum(0));
+            assert_eq!(
+                wwm.prod(.., ..).0,
+                a.iter().map(|&x| x as u128).sum::<u128>() - a[i] as u128
+            );
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use std::{
+    cmp::Ordering,
+    mem,
+    ops::{Bound, RangeBounds},
+};
+
+use crate::{
+    lazy_segtree::{Map, Monoid},
+    simple_rng::Rng,
+};
+
+type Idx = u32;
+const NIL: Idx = !0;
+
+struct Node<T, M> {
+    value: T,
+    prod: T,
+    rev_prod: T,
+    map: M,
+    has_map: bool,
+    rev: bool,
+    len: usize,
+    pri: u64,
+    left: Idx,
+    right: Idx,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Root(Idx);
+
+pub struct Treap<T, M> {
+    nodes: Vec<Node<T, M>>,
+    free: Vec<Idx>,
+    rng: Rng,
+}
+
+impl<T: Monoid, M: Map<T>> Treap<T, M> {
+    pub fn new(rng: Rng) -> Self {
+        Self {
+            nodes: vec![],
+            free: vec![],
+            rng,
+        }
+    }
+
+    pub fn empty(&self) -> Root {
+        Root(NIL)
+    }
+
+    pub fn node(&mut self, x: T) -> Root {
+        Root(self.alloc(x))
+    }
+
+    pub fn build(&mut self, a: impl IntoIterator<Item = T>) -> Root {
+        let mut stk: Vec<Idx> = vec![];
+        for x in a {
+            let v = self.alloc(x);
+            let mut last = NIL;
+            while let Some(&t) = stk.last() {
+                if self.nodes[t as usize].pri >= self.nodes[v as usize].pri {
+                    break;
+                }
+                self.pull(t);
+                last = t;
+                stk.pop();
+            }
+            self.nodes[v as usize].left = last;
+            if let Some(&t) = stk.last() {
+                self.nodes[t as usize].right = v;
+            }
+            stk.push(v);
+        }
+        let root = stk.first().copied().unwrap_or(NIL);
+        while let Some(t) = stk.pop() {
+            self.pull(t);
+        }
+        Root(root)
+    }
+
+    pub fn len(&self, root: Root) -> usize {
+        self.len_of(root.0)
+    }
+
+    pub fn merge(&mut self, a: Root, b: Root) -> Root {
+        Root(self.merge_impl(a.0, b.0))
+    }
+
+    pub fn split_at(&mut self, root: Root, k: usize) -> (Root, Root) {
+        assert!(
+            k <= self.len(root),
+            "out of range (len = {}, index = {k})",
+            self.len(root)
+        );
+        let (a, b) = self.split_impl(root.0, k);
+        (Root(a), Root(b))
+    }
+
+    pub fn insert(&mut self, root: &mut Root, i: usize, x: T) {
+        let (a, b) = self.split_at(*root, i);
+        let v = self.node(x);
+        let a = self.merge(a, v);
+        *root = self.merge(a, b);
+    }
+
+    pub fn remove(&mut self, root: &mut Root, i: usize) -> T {
+        assert!(
+            i < self.len(*root),
+            "out of range (len = {}, index = {i})",
+            self.len(*root)
+        );
+        let (a, b) = self.split_at(*root, i);
+        let (v, b) = self.split_at(b, 1);
+        *root = self.merge(a, b);
+        self.free.push(v.0);
+        mem::replace(&mut self.nodes[v.0 as usize].value, T::id())
+    }
+
+    pub fn get(&mut self, root: Root, i: usize) -> T {
+        assert!(
+            i < self.len(root),
+            "out of range (len = {}, index = {i})",
+            self.len(root)
+        );
+        let mut t = root.0;
+        let mut i = i;
+        loop {
+            self.push(t);
+            let Node { left, right, .. } = self.nodes[t as usize];
+            let len = self.len_of(left);
+            match i.cmp(&len) {
+                Ordering::Less => t = left,
+                Ordering::Equal => return self.nodes[t as usize].value.op(&T::id()),
+                Ordering::Greater => {
+                    i -= len + 1;
+                    t = right;
+                }
+            }
+        }
+    }
+
+    pub fn prod(&mut self, root: Root, range: impl RangeBounds<usize>) -> T {
+        let (l, r) = self.range(root, range);
+        self.prod_impl(root.0, l, r)
+    }
+
+    pub fn apply(&mut self, root: Root, range: impl RangeBounds<usize>, map: M) {
+        let (l, r) = self.range(root, range);
+        self.apply_impl(root.0, l, r, &map);
+    }
+
+    pub fn reverse(&mut self, root: &mut Root, range: impl RangeBounds<usize>) {
+        let (l, r) = self.range(*root, range);
+        let (a, b) = self.split_impl(root.0, l);
+        let (b, c) = self.split_impl(b, r - l);
+        if b != NIL {
+            self.toggle(b);
+        }
+        let b = self.merge_impl(b, c);
+        *root = Root(self.merge_impl(a, b));
+    }
+
+    pub fn to_vec(&mut self, root: Root) -> Vec<T> {
+        let mut res = Vec::with_capacity(self.len(root));
+        self.collect(root.0, &mut res);
+        res
+    }
+
+    fn collect(&mut self, t: Idx, res: &mut Vec<T>) {
+        if t == NIL {
+            return;
+        }
+        self.push(t);
+        let Node { left, right, .. } = self.nodes[t as usize];
+        self.collect(left, res);
+        res.push(self.nodes[t as usize].value.op(&T::id()));
+        self.collect(right, res);
+    }
+
+    fn alloc(&mut self, x: T) -> Idx {
+        let node = Node {
+            prod: x.op(&T::id()),
+            rev_prod: x.op(&T::id()),
+            value: x,
+            map: M::id(),
+            has_map: false,
+            rev: false,
+            len: 1,
+            pri: self.rng.next_u64(),
+            left: NIL,
+            right: NIL,
+        };
+        if let Some(i) = self.free.pop() {
+            self.nodes[i as usize] = node;
+            i
+        } else {
+            self.nodes.push(node);
+            (self.nodes.len() - 1) as Idx
+        }
+    }
+
+    fn len_of(&self, t: Idx) -> usize {
+        if t == NIL {
+            0
+        } else {
+            self.nodes[t as usize].len
+        }
+    }
+
+    fn pull(&mut self, t: Idx) {
+        let Node { left, right, .. } = self.nodes[t as usize];
+        let mut len = 1;
+        let node = &self.nodes[t as usize];
+        let mut prod = node.value.op(&T::id());
+        let mut rev_prod = node.value.op(&T::id());
+        if left != NIL {
+            let l = &self.nodes[left as usize];
+            len += l.len;
+            prod = l.prod.op(&prod);
+            rev_prod = rev_prod.op(&l.rev_prod);
+        }
+        if right != NIL {
+            let r = &self.nodes[right as usize];
+            len += r.len;
+            prod = prod.op(&r.prod);
+            rev_prod = r.rev_prod.op(&rev_prod);
+        }
+        let node = &mut self.nodes[t as usize];
+        node.len = len;
+        node.prod = prod;
+        node.rev_prod = rev_prod;
+    }
+
+    fn act(&mut self, t: Idx, map: &M) {
+        let node = &mut self.nodes[t as usize];
+        node.value = map.map(&node.value);
+        node.prod = map.map(&node.prod);
+        node.rev_prod = map.map(&node.rev_prod);
+        node.map = map.comp(&node.map);
+        node.has_map = true;
+    }
+
+    fn toggle(&mut self, t: Idx) {
+        let node = &mut self.nodes[t as usize];
+        mem::swap(&mut node.left, &mut node.right);
+        mem::swap(&mut node.prod, &mut node.rev_prod);
+        node.rev ^= true;
+    }
+
+    fn push(&mut self, t: Idx) {
+        let Node { left, right, .. } = self.nodes[t as usize];
+        if self.nodes[t as usize].has_map {
+            let map = mem::replace(&mut self.nodes[t as usize].map, M::id());
+            self.nodes[t as usize].has_map = false;
+            for c in [left, right] {
+                if c != NIL {
+                    self.act(c, &map);
+                }
+            }
+        }
+        if self.nodes[t as usize].rev {
+            self.nodes[t as usize].rev = false;
+            for c in [left, right] {
+                if c != NIL {
+                    self.toggle(c);
+                }
+            }
+        }
+    }
+
+    fn merge_impl(&mut self, a: Idx, b: Idx) -> Idx {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+        if self.nodes[a as usize].pri > self.nodes[b as usize].pri {
+            self.push(a);
+            let right = self.merge_impl(self.nodes[a as usize].right, b);
+            self.nodes[a as usize].right = right;
+            self.pull(a);
+            a
+        } else {
+            self.push(b);
+            let left = self.merge_impl(a, self.nodes[b as usize].left);
+            self.nodes[b as usize].left = left;
+            self.pull(b);
+            b
+        }
+    }
+
+    fn split_impl(&mut self, t: Idx, k: usize) -> (Idx, Idx) {
+        if t == NIL {
+            return (NIL, NIL);
+        }
+        self.push(t);
+        let Node { left, right, .. } = self.nodes[t as usize];
+        let len = self.len_of(left);
+        if k <= len {
+            let (a, b) = self.split_impl(left, k);
+            self.nodes[t as usize].left = b;
+            self.pull(t);
+            (a, t)
+        } else {
+            let (a, b) = self.split_impl(right, k - len - 1);
+            self.nodes[t as usize].right = a;
+            self.pull(t);
+            (t, b)
+        }
+    }
+
+    fn prod_impl(&mut self, t: Idx, l: usize, r: usize) -> T {
+        if t == NIL || l >= r {
+            return T::id();
+        }
+        let node = &self.nodes[t as usize];
+        if l == 0 && r == node.len {
+            return node.prod.op(&T::id());
+        }
+        self.push(t);
+        let Node { left, right, .. } = self.nodes[t as usize];
+        let len = self.len_of(left);
+        let mut x = self.prod_impl(left, l, r.min(len));
+        if l <= len && len < r {
+            x = x.op(&self.nodes[t as usize].value);
+        }
+        let y = self.prod_impl(right, l.max(len + 1) - len - 1, r.max(len + 1) - len - 1);
+        x.op(&y)
+    }
+
+    fn apply_impl(&mut self, t: Idx, l: usize, r: usize, map: &M) {
+        if t == NIL || l >= r {
+            return;
+        }
+        if l == 0 && r == self.nodes[t as usize].len {
+            self.act(t, map);
+            return;
+        }
+        self.push(t);
+        let Node { left, right, .. } = self.nodes[t as usize];
+        let len = self.len_of(left);
+        self.apply_impl(left, l, r.min(len), map);
+        if l <= len && len < r {
+            let node = &mut self.nodes[t as usize];
+            node.value = map.map(&node.value);
+        }
+        self.apply_impl(
+            right,
+            l.max(len + 1) - len - 1,
+            r.max(len + 1) - len - 1,
+            map,
+        );
+        self.pull(t);
+    }
+
+    fn range(&self, root: Root, range: impl RangeBounds<usize>) -> (usize, usize) {
+        let n = self.len(root);
+        let l = match range.start_bound() {
+            Bound::Included(&l) => l,
+            Bound::Excluded(&l) => l + 1,
+            Bound::Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Bound::Included(&r) => r + 1,
+            Bound::Excluded(&r) => r,
+            Bound::Unbounded => n,
+        };
+        assert!(
+            l <= r && r <= n,
+            "out of range (len = {n}, l = {l}, r = {r})"
+        );
+        (l, r)
+    }
+}
@@ -1,12 +1,14 @@
 #[derive(Clone)]
 pub struct Trie {
     nodes: Vec<Node>,
+    pass: Vec<usize>,
+    end: Vec<usize>,
 }
 
 type Bits = u64;
 
 #[derive(Clone)]
-struct Node {
+pub(crate) struct Node {
     bits: [Bits; 4],
     edges: Vec<usize>,
 }
@@ -15,6 +17,8 @@ impl Trie {
     pub fn new() -> Self {
         Self {
             nodes: vec![Node::new()],
+            pass: vec![0],
+            end: vec![0],
         }
     }
 
@@ -26,11 +30,20 @@ impl Trie {
         self.nodes[i].transition(c)
     }
 
-    pub fn links(&self, i: usize) -> Links {
-        Links {
-            node: &self.nodes[i],
-            c: Some(0),
-        }
+    pub fn links(&self, i: usize) -> Links<'_> {
+        self.nodes[i].links()
+    }
+
+    pub fn pass_count(&self, i: usize) -> usize {
+        self.pass[i]
+    }
+
+    pub fn end_count(&self, i: usize) -> usize {
+        self.end[i]
+    }
+
+    pub fn len(&self) -> usize {
+        self.pass[0]
     }
 
     pub fn insert_slice(&mut self, s: &[u8]) -> (bool, usize) {
@@ -39,31 +52,147 @@ impl Trie {
 
     pub fn insert(&mut self, s: impl IntoIterator<Item = u8>) -> (bool, usize) {
         let mut i = 0;
-        let mut inserted = false;
+        self.pass[0] += 1;
         for c in s {
-            let len = self.nodes.len();
-            if let Some(node) = self.nodes.get_mut(i) {
-                if node.edges.is_empty() {
-                    inserted = true;
-                    node.set_bit(c);
-                    node.edges.push(len);
-                    i = len;
+            i = match self.nodes[i].transition(c) {
+                Some(j) => j,
+                None => {
+                    let j = self.nodes.len();
+                    self.nodes[i].set_transition(c, j);
                     self.nodes.push(Node::new());
-                } else {
-                    let (exists, rank) = node.rank(c);
-                    if !exists {
-                        inserted = true;
-                        node.set_bit(c);
-                        node.edges.insert(rank, len);
-                        i = len;
-                        self.nodes.push(Node::new());
-                    } else {
-                        i = node.edges[rank];
-                    }
+                    self.pass.push(0);
+                    self.end.push(0);
+                    j
                 }
+            };
+            self.pass[i] += 1;
+        }
+        self.end[i] += 1;
+        (self.end[i] == 1, i)
+    }
+
+    pub fn find(&self, s: impl IntoIterator<Item = u8>) -> Option<usize> {
+        let mut i = 0;
+        for c in s {
+            i = self.transition(i, c)?;
+        }
+        Some(i)
+    }
+
+    pub fn count(&self, s: impl IntoIterator<Item = u8>) -> usize {
+        self.find(s).map_or(0, |i| self.end[i])
+    }
+
+    pub fn contains(&self, s: impl IntoIterator<Item = u8>) -> bool {
+        self.count(s) > 0
+    }
+
+    pub fn remove(&mut self, s: impl IntoIterator<Item = u8> + Clone) -> bool {
+        if !self.contains(s.clone()) {
+            return false;
+        }
+        let mut i = 0;
+        let mut dead = None;
+        self.pass[0] -= 1;
+        for c in s {
+            let j = self.transition(i, c).unwrap();
+            self.pass[j] -= 1;
+            if self.pass[j] == 0 && dead.is_none() {
+                dead = Some((i, c));
+            }
+            i = j;
+        }
+        self.end[i] -= 1;
+        if let Some((i, c)) = dead {
+            self.nodes[i].remove_transition(c);
+        }
+        true
+    }
+
+    pub fn longest_prefix_match(&self, s: impl IntoIterator<Item = u8>) -> Option<(usize, usize)> {
+        let mut i = 0;
+        let mut res = (self.end[0] > 0).then_some((0, 0));
+        for (k, c) in s.into_iter().enumerate() {
+            match self.transition(i, c) {
+                Some(j) => i = j,
+                None => break,
+            }
+            if self.end[i] > 0 {
+                res = Some((k + 1, i));
             }
         }
-        (inserted, i)
+        res
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            trie: self,
+            stack: vec![self.links(0)],
+            key: vec![],
+            root: true,
+        }
+    }
+
+    pub fn xor_max(&self, x: u64, width: u32) -> Option<u64> {
+        self.xor_greedy(x, width, 1)
+    }
+
+    pub fn xor_min(&self, x: u64, width: u32) -> Option<u64> {
+        self.xor_greedy(x, width, 0)
+    }
+
+    fn xor_greedy(&self, x: u64, width: u32, prefer: u8) -> Option<u64> {
+        if self.len() == 0 {
+            return None;
+        }
+        let mut i = 0;
+        let mut res = 0;
+        for c in bits(x, width) {
+            let b = c ^ prefer;
+            let (j, d) = match self.transition(i, b) {
+                Some(j) => (j, prefer),
+                None => (self.transition(i, b ^ 1)?, prefer ^ 1),
+            };
+            i = j;
+            res = res << 1 | d as u64;
+        }
+        Some(res)
+    }
+}
+
+pub fn bits(x: u64, width: u32) -> impl Iterator<Item = u8> + Clone {
+    (0..width).rev().map(move |d| (x >> d & 1) as u8)
+}
+
+pub struct Iter<'a> {
+    trie: &'a Trie,
+    stack: Vec<Links<'a>>,
+    key: Vec<u8>,
+    root: bool,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = (Vec<u8>, usize);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.root {
+            self.root = false;
+            if self.trie.end[0] > 0 {
+                return Some((vec![], self.trie.end[0]));
+            }
+        }
+        while let Some(links) = self.stack.last_mut() {
+            let Some((c, v)) = links.next() else {
+                self.stack.pop();
+                self.key.pop();
+                continue;
+            };
+            self.key.push(c);
+            self.stack.push(self.trie.links(v));
+            if self.trie.end[v] > 0 {
+                return Some((self.key.clone(), self.trie.end[v]));
+            }
+        }
+        None
     }
 }
 
@@ -74,7 +203,7 @@ impl Default for Trie {
 }
 
 impl Node {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             bits: [0; 4],
             edges: vec![],
@@ -82,11 +211,40 @@ impl Node {
     }
 
     #[inline]
-    fn transition(&self, c: u8) -> Option<usize> {
+    pub(crate) fn transition(&self, c: u8) -> Option<usize> {
         let (exists, i) = self.rank(c);
         exists.then(|| self.edges[i])
     }
 
+    #[inline]
+    pub(crate) fn set_transition(&mut self, c: u8, v: usize) {
+        let (exists, i) = self.rank(c);
+        if exists {
+            self.edges[i] = v;
+        } else {
+            self.set_bit(c);
+            self.edges.insert(i, v);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn remove_transition(&mut self, c: u8) {
+        let (exists, i) = self.rank(c);
+        if exists {
+            let (q, r) = index_bit(c);
+            self.bits[q] &= !(1 << r);
+            self.edges.remove(i);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn links(&self) -> Links<'_> {
+        Links {
+            node: self,
+            c: Some(0),
+        }
+    }
+
     #[inline]
     fn set_bit(&mut self, c: u8) {
         let (q, r) = index_bit(c);
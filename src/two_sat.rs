@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use crate::{adj_list::*, scc::*};
 
 #[derive(Clone, Debug)]
@@ -12,6 +14,16 @@ impl TwoSat {
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.g.num_vert() / 2
+    }
+
+    pub fn add_var(&mut self) -> usize {
+        self.g.add_vert();
+        self.g.add_vert();
+        self.len() - 1
+    }
+
     #[inline]
     pub fn clause(&mut self, i: usize, f: bool, j: usize, g: bool) {
         let u = 2 * i + f as usize;
@@ -20,8 +32,50 @@ impl TwoSat {
         self.g.edge(v ^ 1, u);
     }
 
-    pub fn solve(self) -> Option<Vec<bool>> {
-        let g = self.g.build();
+    #[inline]
+    pub fn implies(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        self.clause(i, !f, j, g);
+    }
+
+    #[inline]
+    pub fn equal(&mut self, i: usize, f: bool, j: usize, g: bool) {
+        self.implies(i, f, j, g);
+        self.implies(j, g, i, f);
+    }
+
+    #[inline]
+    pub fn set_true(&mut self, i: usize, f: bool) {
+        self.clause(i, f, i, f);
+    }
+
+    pub fn at_most_one(&mut self, lits: &[(usize, bool)]) -> Range<usize> {
+        let start = self.len();
+        let Some((&(i, f), lits)) = lits.split_first() else {
+            return start..start;
+        };
+        let mut s = self.add_var();
+        self.implies(i, f, s, true);
+        for &(i, f) in lits {
+            let t = self.add_var();
+            self.implies(i, f, t, true);
+            self.implies(s, true, t, true);
+            self.implies(s, true, i, !f);
+            s = t;
+        }
+        start..self.len()
+    }
+
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        self.solve_with(&[])
+    }
+
+    pub fn solve_with(&self, assumptions: &[(usize, bool)]) -> Option<Vec<bool>> {
+        let mut g = self.g.clone();
+        for &(i, f) in assumptions {
+            let u = 2 * i + f as usize;
+            g.edge(u ^ 1, u);
+        }
+        let g = g.build();
         let n = g.num_vert() / 2;
         let scc = scc(g.num_vert(), |u| g.adj(u));
         let mut res = vec![false; n];
@@ -0,0 +1,227 @@
+use std::ops::{Bound, RangeBounds};
+
+use crate::{
+    bitset::RankSelect,
+    segtree::{Monoid, SegTree},
+};
+
+#[derive(Clone, Debug)]
+pub struct WaveletMatrix {
+    len: usize,
+    mats: Vec<RankSelect>,
+    zeros: Vec<usize>,
+}
+
+impl WaveletMatrix {
+    pub fn new(a: &[u64]) -> Self {
+        let bits = a.iter().max().map_or(0, |&x| u64::BITS - x.leading_zeros());
+        let mut a = a.to_vec();
+        let mut mats = Vec::with_capacity(bits as usize);
+        let mut zeros = Vec::with_capacity(bits as usize);
+        for d in (0..bits).rev() {
+            let mat: RankSelect = a.iter().map(|&x| x >> d & 1 != 0).collect();
+            zeros.push(mat.rank0(a.len()));
+            mats.push(mat);
+            let (mut lo, hi): (Vec<u64>, Vec<u64>) = a.iter().partition(|&&x| x >> d & 1 == 0);
+            lo.extend(hi);
+            a = lo;
+        }
+        Self {
+            len: a.len(),
+            mats,
+            zeros,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.mats.len() as u32
+    }
+
+    #[inline]
+    fn step(&self, d: usize, i: usize, f: bool) -> usize {
+        if f {
+            self.zeros[d] + self.mats[d].rank1(i)
+        } else {
+            self.mats[d].rank0(i)
+        }
+    }
+
+    pub fn get(&self, mut i: usize) -> u64 {
+        assert!(
+            i < self.len,
+            "out of range (len = {}, index = {i})",
+            self.len
+        );
+        let mut x = 0;
+        for d in 0..self.mats.len() {
+            let f = self.mats[d].bit(i);
+            x = x << 1 | f as u64;
+            i = self.step(d, i, f);
+        }
+        x
+    }
+
+    pub fn kth_smallest(&self, range: impl RangeBounds<usize>, mut k: usize) -> Option<u64> {
+        let (mut l, mut r) = self.range(range);
+        if k >= r - l {
+            return None;
+        }
+        let mut x = 0;
+        for d in 0..self.mats.len() {
+            let z = self.mats[d].rank0(r) - self.mats[d].rank0(l);
+            let f = k >= z;
+            if f {
+                k -= z;
+            }
+            x = x << 1 | f as u64;
+            l = self.step(d, l, f);
+            r = self.step(d, r, f);
+        }
+        Some(x)
+    }
+
+    pub fn kth_largest(&self, range: impl RangeBounds<usize>, k: usize) -> Option<u64> {
+        let (l, r) = self.range(range);
+        (k < r - l)
+            .then(|| self.kth_smallest(l..r, r - l - 1 - k))
+            .flatten()
+    }
+
+    fn count_lt(&self, mut l: usize, mut r: usize, x: u128) -> usize {
+        if x >> self.mats.len() != 0 {
+            return r - l;
+        }
+        let mut res = 0;
+        for d in 0..self.mats.len() {
+            let f = x >> (self.mats.len() - 1 - d) & 1 != 0;
+            if f {
+                res += self.mats[d].rank0(r) - self.mats[d].rank0(l);
+            }
+            l = self.step(d, l, f);
+            r = self.step(d, r, f);
+        }
+        res
+    }
+
+    pub fn count(&self, range: impl RangeBounds<usize>, values: impl RangeBounds<u64>) -> usize {
+        let (l, r) = self.range(range);
+        let (lo, hi) = value_range(values);
+        if lo >= hi {
+            return 0;
+        }
+        self.count_lt(l, r, hi) - self.count_lt(l, r, lo)
+    }
+
+    pub fn prev_value(&self, range: impl RangeBounds<usize>, upper: u64) -> Option<u64> {
+        let (l, r) = self.range(range);
+        let k = self.count_lt(l, r, upper as u128).checked_sub(1)?;
+        self.kth_smallest(l..r, k)
+    }
+
+    pub fn next_value(&self, range: impl RangeBounds<usize>, lower: u64) -> Option<u64> {
+        let (l, r) = self.range(range);
+        let k = self.count_lt(l, r, lower as u128);
+        self.kth_smallest(l..r, k)
+    }
+
+    fn range(&self, range: impl RangeBounds<usize>) -> (usize, usize) {
+        let l = match range.start_bound() {
+            Bound::Included(&l) => l,
+            Bound::Excluded(&l) => l + 1,
+            Bound::Unbounded => 0,
+        };
+        let r = match range.end_bound() {
+            Bound::Included(&r) => r + 1,
+            Bound::Excluded(&r) => r,
+            Bound::Unbounded => self.len,
+        };
+        assert!(
+            l <= r && r <= self.len,
+            "out of range (len = {}, l = {l}, r = {r})",
+            self.len
+        );
+        (l, r)
+    }
+}
+
+fn value_range(values: impl RangeBounds<u64>) -> (u128, u128) {
+    let lo = match values.start_bound() {
+        Bound::Included(&l) => l as u128,
+        Bound::Excluded(&l) => l as u128 + 1,
+        Bound::Unbounded => 0,
+    };
+    let hi = match values.end_bound() {
+        Bound::Included(&r) => r as u128 + 1,
+        Bound::Excluded(&r) => r as u128,
+        Bound::Unbounded => 1 << 64,
+    };
+    (lo, hi)
+}
+
+pub struct WeightedWaveletMatrix<T> {
+    wm: WaveletMatrix,
+    seg: Vec<SegTree<T>>,
+}
+
+impl<T: Monoid> WeightedWaveletMatrix<T> {
+    pub fn new(a: &[u64]) -> Self {
+        let wm = WaveletMatrix::new(a);
+        let seg = (0..=wm.mats.len()).map(|_| SegTree::new(a.len())).collect();
+        Self { wm, seg }
+    }
+
+    pub fn matrix(&self) -> &WaveletMatrix {
+        &self.wm
+    }
+
+    pub fn set_weight(&mut self, mut i: usize, w: T) {
+        assert!(
+            i < self.wm.len,
+            "out of range (len = {}, index = {i})",
+            self.wm.len
+        );
+        self.seg[0].set(i, w.op(&T::id()));
+        for d in 0..self.wm.mats.len() {
+            i = self.wm.step(d, i, self.wm.mats[d].bit(i));
+            self.seg[d + 1].set(i, w.op(&T::id()));
+        }
+    }
+
+    pub fn prod(&self, range: impl RangeBounds<usize>, values: impl RangeBounds<u64>) -> T {
+        let (l, r) = self.wm.range(range);
+        let (lo, hi) = value_range(values);
+        self.prod_rec(0, l, r, 0, lo, hi)
+    }
+
+    fn prod_rec(&self, d: usize, l: usize, r: usize, x: u128, lo: u128, hi: u128) -> T {
+        let bits = self.wm.mats.len() - d;
+        let (x_lo, x_hi) = (x << bits, x + 1 << bits);
+        if l == r || hi <= x_lo || x_hi <= lo {
+            return T::id();
+        }
+        if lo <= x_lo && x_hi <= hi {
+            return self.seg[d].prod(l..r);
+        }
+        let zero = self.prod_rec(
+            d + 1,
+            self.wm.step(d, l, false),
+            self.wm.step(d, r, false),
+            x << 1,
+            lo,
+            hi,
+        );
+        let one = self.prod_rec(
+            d + 1,
+            self.wm.step(d, l, true),
+            self.wm.step(d, r, true),
+            x << 1 | 1,
+            lo,
+            hi,
+        );
+        zero.op(&one)
+    }
+}